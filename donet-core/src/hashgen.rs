@@ -28,49 +28,113 @@ pub trait LegacyDCHash {
     fn generate_hash(&self, hashgen: &mut DCHashGenerator);
 }
 
-/// Prime number generator based off Panda's.
+/// Prime number generator based off Panda's, backed by a sieve of
+/// Eratosthenes instead of per-candidate trial division.
+///
+/// Primes are cached in a `Vec<u32>` (trial division against a `Vec<u16>`
+/// overflows once a cached prime exceeds 255, since 256² doesn't fit in a
+/// `u16`), and the sieve bound doubles whenever the cache runs short of
+/// the requested index, so generating the first `n` primes costs roughly
+/// `O(bound log log bound)` total instead of `O(n * sqrt(bound))`.
 pub struct PrimeNumberGenerator {
-    primes: Vec<u16>,
+    primes: Vec<u32>,
+    /// The inclusive upper bound the sieve was last run against; `primes`
+    /// holds every prime up to and including this bound.
+    sieve_bound: u32,
 }
 
 impl Default for PrimeNumberGenerator {
     fn default() -> Self {
-        Self { primes: vec![2_u16] }
+        Self { primes: vec![2_u32], sieve_bound: 2 }
     }
 }
 
 impl PrimeNumberGenerator {
-    /// Returns the nth prime number. this\[0\] returns 2, this\[1\] returns 3;
-    /// successively larger values of n return larger prime numbers, up to the
-    /// largest prime number that can be represented in an int.
-    pub fn get_prime(&mut self, n: u16) -> u16 {
-        // Compute the prime numbers between the last-computed prime number and n.
-        let mut candidate: u16 = self.primes.last().unwrap() + 1_u16;
-
-        while self.primes.len() <= usize::from(n) {
-            // Is candidate prime?  It is not if any one of the already-found prime
-            // numbers (up to its square root) divides it evenly.
-            let mut maybe_prime: bool = true;
-            let mut j: usize = 0;
-
-            while maybe_prime && self.primes.get(j).unwrap() * self.primes.get(j).unwrap() <= candidate {
-                if (self.primes.get(j).unwrap() * (candidate / self.primes.get(j).unwrap())) == candidate {
-                    // This one is not prime.
-                    maybe_prime = false;
-                }
-                j += 1;
-                assert!(j < self.primes.len());
+    /// Starting sieve bound; large enough to cover `MAX_PRIME_NUMBERS`
+    /// primes in a handful of doublings without over-allocating up front.
+    const INITIAL_SIEVE_BOUND: u32 = 4096;
+
+    /// Runs the sieve of Eratosthenes up to (and including) `bound`,
+    /// returning every prime in that range in ascending order.
+    fn sieve(bound: u32) -> Vec<u32> {
+        let mut is_composite = vec![false; bound as usize + 1];
+        let mut primes = Vec::new();
+
+        for candidate in 2..=bound {
+            if is_composite[candidate as usize] {
+                continue;
             }
+            primes.push(candidate);
 
-            if maybe_prime {
-                self.primes.push(candidate);
+            // Mark composites of `candidate`, starting at its square (in
+            // u64 so the multiply can't overflow a u32 for large primes).
+            let mut multiple = u64::from(candidate) * u64::from(candidate);
+            while multiple <= u64::from(bound) {
+                is_composite[multiple as usize] = true;
+                multiple += u64::from(candidate);
             }
-            candidate += 1;
         }
-        *self.primes.get(usize::from(n)).unwrap()
+        primes
+    }
+
+    /// Grows the sieve bound (doubling it) until the cache holds at least
+    /// `target_len` primes, then replaces `self.primes` with the result.
+    fn ensure(&mut self, target_len: usize) {
+        let mut bound = self.sieve_bound;
+
+        while self.primes.len() < target_len {
+            bound = if bound < Self::INITIAL_SIEVE_BOUND {
+                Self::INITIAL_SIEVE_BOUND
+            } else {
+                bound.saturating_mul(2)
+            };
+            self.primes = Self::sieve(bound);
+            self.sieve_bound = bound;
+        }
+    }
+
+    /// Returns the nth prime number as a `u32`. this\[0\] returns 2, this\[1\]
+    /// returns 3; successively larger values of n return larger prime
+    /// numbers, for any n up to `u16::MAX`.
+    pub fn get_prime_u32(&mut self, n: u16) -> u32 {
+        self.ensure(usize::from(n) + 1);
+        self.primes[usize::from(n)]
+    }
+
+    /// Returns the nth prime number. this\[0\] returns 2, this\[1\] returns 3;
+    /// successively larger values of n return larger prime numbers, up to the
+    /// largest prime number that can be represented in a `u16`.
+    ///
+    /// Panics if the nth prime doesn't fit in a `u16`; callers past that
+    /// range should use [`Self::get_prime_u32`] instead.
+    pub fn get_prime(&mut self, n: u16) -> u16 {
+        self.get_prime_u32(n)
+            .try_into()
+            .expect("nth prime exceeds u16::MAX; use get_prime_u32 instead")
     }
 }
 
+/// Selects the width of a [`DCHashGenerator`]'s running accumulator.
+///
+/// `Bits32` reproduces the legacy Panda3D DC hash exactly (truncated to
+/// the low-order 32 bits), for clients that still expect that format.
+/// `Bits64` widens the accumulator so servers that have outgrown the
+/// 32-bit hash's collision resistance can negotiate a wider one instead,
+/// using the same prime-weighted scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashWidth {
+    Bits32,
+    Bits64,
+}
+
+/// The running accumulator backing a [`DCHashGenerator`], sized per its
+/// [`HashWidth`].
+#[derive(Debug, Clone, Copy)]
+enum HashAccumulator {
+    Bits32(i32),
+    Bits64(i64),
+}
+
 /// The following is an excerpt from Panda3D's source:
 ///
 /// We multiply each consecutive integer by the next prime number and add it to
@@ -81,19 +145,53 @@ impl PrimeNumberGenerator {
 /// growing insanely large, however (and to avoid wasting time computing large
 /// prime numbers unnecessarily), and we also truncate the result to the low-
 /// order 32 bits.
-#[derive(Default)]
+///
+/// The truncation above is specific to [`HashWidth::Bits32`]; see
+/// [`HashWidth`] for the wider alternative.
 pub struct DCHashGenerator {
-    hash: i32,
+    hash: HashAccumulator,
     index: u16,
     primes: PrimeNumberGenerator,
 }
 
+impl Default for DCHashGenerator {
+    /// Defaults to [`HashWidth::Bits32`], matching the legacy Panda3D hash.
+    fn default() -> Self {
+        Self::new(HashWidth::Bits32)
+    }
+}
+
 impl DCHashGenerator {
+    /// Creates a hash generator accumulating into the given [`HashWidth`].
+    pub fn new(width: HashWidth) -> Self {
+        let hash = match width {
+            HashWidth::Bits32 => HashAccumulator::Bits32(0),
+            HashWidth::Bits64 => HashAccumulator::Bits64(0),
+        };
+
+        Self { hash, index: 0, primes: PrimeNumberGenerator::default() }
+    }
+
     /// Adds another integer to the hash so far.
     pub fn add_int(&mut self, number: i32) {
         assert!(self.index < MAX_PRIME_NUMBERS);
 
-        self.hash += i32::from(self.primes.get_prime(self.index)) * number;
+        // Multiply in a width at least as wide as the accumulator, so a
+        // large prime times a large `number` can't overflow before it's
+        // folded (and truncated, for `Bits32`) back into the hash. Use
+        // `get_prime_u32`, not `get_prime`: `index` ranges up to
+        // `MAX_PRIME_NUMBERS`, well past the primes that fit in a u16.
+        let prime: i64 = i64::from(self.primes.get_prime_u32(self.index));
+
+        self.hash = match self.hash {
+            HashAccumulator::Bits32(h) => {
+                let term = (prime * i64::from(number)) as i32;
+                HashAccumulator::Bits32(h.wrapping_add(term))
+            }
+            HashAccumulator::Bits64(h) => {
+                HashAccumulator::Bits64(h.wrapping_add(prime.wrapping_mul(i64::from(number))))
+            }
+        };
         self.index = (self.index + 1) % MAX_PRIME_NUMBERS;
     }
 
@@ -111,14 +209,43 @@ impl DCHashGenerator {
         self.add_blob(string.into_bytes());
     }
 
-    pub const fn get_hash(&self) -> DCFileHash {
-        self.hash as u32
+    /// Returns the accumulated hash as little-endian bytes: 4 bytes for
+    /// [`HashWidth::Bits32`], 8 bytes for [`HashWidth::Bits64`].
+    pub fn get_hash_bytes(&self) -> Vec<u8> {
+        match self.hash {
+            HashAccumulator::Bits32(h) => (h as u32).to_le_bytes().to_vec(),
+            HashAccumulator::Bits64(h) => (h as u64).to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Returns the accumulated hash as a `u32`. Exact for a `Bits32`
+    /// generator; for `Bits64`, returns only the low-order 32 bits.
+    pub fn as_u32(&self) -> u32 {
+        match self.hash {
+            HashAccumulator::Bits32(h) => h as u32,
+            HashAccumulator::Bits64(h) => h as u32,
+        }
+    }
+
+    /// Returns the accumulated hash as a `u64`. Exact for a `Bits64`
+    /// generator; for `Bits32`, the value is zero-extended, not resized.
+    pub fn as_u64(&self) -> u64 {
+        match self.hash {
+            HashAccumulator::Bits32(h) => u64::from(h as u32),
+            HashAccumulator::Bits64(h) => h as u64,
+        }
+    }
+
+    /// Equivalent to [`Self::as_u32`]; kept for callers built against the
+    /// original, `Bits32`-only hash generator.
+    pub fn get_hash(&self) -> DCFileHash {
+        self.as_u32()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::PrimeNumberGenerator;
+    use super::{DCHashGenerator, HashWidth, PrimeNumberGenerator};
 
     #[test]
     fn prime_number_generator_integrity() {
@@ -139,4 +266,61 @@ mod tests {
             assert_eq!(target_prime, generator.get_prime(i.try_into().unwrap()));
         }
     }
+
+    #[test]
+    fn prime_number_generator_beyond_u16() {
+        let mut generator: PrimeNumberGenerator = PrimeNumberGenerator::default();
+
+        // The 6543rd prime (1-indexed) is 65537, the smallest prime that
+        // doesn't fit in a u16; get_prime_u32 must still return it
+        // correctly once the sieve has grown past its initial bound.
+        assert_eq!(generator.get_prime_u32(6542), 65537);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds u16::MAX")]
+    fn prime_number_generator_get_prime_panics_past_u16() {
+        let mut generator: PrimeNumberGenerator = PrimeNumberGenerator::default();
+        generator.get_prime(6542);
+    }
+
+    #[test]
+    fn hash_generator_default_is_bits32_and_matches_get_hash() {
+        let mut generator = DCHashGenerator::default();
+        generator.add_string("DistributedAvatar".to_string());
+
+        assert_eq!(generator.as_u32(), generator.get_hash());
+        assert_eq!(generator.get_hash_bytes().len(), 4);
+        assert_eq!(generator.get_hash_bytes(), generator.as_u32().to_le_bytes());
+    }
+
+    #[test]
+    fn hash_generator_add_int_reaches_max_prime_numbers_without_panicking() {
+        // `add_int` cycles `index` through the full `0..MAX_PRIME_NUMBERS`
+        // range, which passes the 6542nd prime (the largest that still fits
+        // in a u16); it must keep going via `get_prime_u32` instead of
+        // panicking like `get_prime` does past that point.
+        let mut generator = DCHashGenerator::default();
+        for i in 0..crate::globals::MAX_PRIME_NUMBERS {
+            generator.add_int(i32::from(i));
+        }
+    }
+
+    #[test]
+    fn hash_generator_bits64_widens_the_accumulator() {
+        let mut bits32 = DCHashGenerator::new(HashWidth::Bits32);
+        let mut bits64 = DCHashGenerator::new(HashWidth::Bits64);
+
+        // Large enough that `2 * number` overflows an i32, so a Bits32
+        // generator wraps on every add while a Bits64 one does not.
+        for _ in 0..3 {
+            bits32.add_int(1_500_000_000);
+            bits64.add_int(1_500_000_000);
+        }
+
+        assert_eq!(bits64.get_hash_bytes().len(), 8);
+        // A Bits64 generator isn't just a Bits32 hash zero-extended: its
+        // accumulator carries bits a 32-bit truncation would have dropped.
+        assert_ne!(bits64.as_u64(), u64::from(bits32.as_u32()));
+    }
 }
@@ -0,0 +1,159 @@
+// DONET SOFTWARE
+// Copyright (c) 2024, Donet Authors.
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3.
+// You should have received a copy of this license along
+// with this source code in a file named "LICENSE."
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program; if not, write to the Free Software Foundation,
+// Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+
+//! DNS SRV-based peer resolution for locating Donet cluster nodes
+//! (message directors, state servers, ...) without hard-coded addresses.
+
+use log::{debug, info};
+use rand::Rng;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use trust_dns_resolver::error::ResolveError;
+use trust_dns_resolver::TokioAsyncResolver;
+
+/// A single resolved peer target, as found in a DNS SRV record.
+#[derive(Debug, Clone)]
+pub struct PeerTarget {
+    pub host: String,
+    pub address: IpAddr,
+    pub port: u16,
+    pub priority: u16,
+    pub weight: u16,
+}
+
+struct CacheEntry {
+    targets: Vec<PeerTarget>,
+    expires_at: Instant,
+}
+
+/// Resolves a Donet cluster service name (e.g. `"_donet._tcp.cluster.local"`)
+/// to its set of `(host, port, priority, weight)` targets via DNS SRV
+/// records, caching results until their TTL expires.
+pub struct ServiceResolver {
+    resolver: TokioAsyncResolver,
+    cache: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ServiceResolver {
+    /// Builds a resolver using the system's configured DNS settings
+    /// (e.g. `/etc/resolv.conf` on Unix).
+    pub fn new() -> Result<Self, ResolveError> {
+        Ok(Self {
+            resolver: TokioAsyncResolver::tokio_from_system_conf()?,
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Resolves `service_name` to its set of peer targets, returning a
+    /// cached result if one is still within its TTL.
+    pub async fn resolve(&self, service_name: &str) -> Result<Vec<PeerTarget>, ResolveError> {
+        if let Some(cached) = self.cached_targets(service_name) {
+            debug!("Using cached SRV targets for {}.", service_name);
+            return Ok(cached);
+        }
+
+        let srv_lookup = self.resolver.srv_lookup(service_name).await?;
+        let mut targets: Vec<PeerTarget> = vec![];
+        let mut min_ttl: u32 = u32::MAX;
+
+        for srv in srv_lookup.iter() {
+            let host: String = srv.target().to_string();
+            let lookup = self.resolver.lookup_ip(host.as_str()).await?;
+
+            for address in lookup.iter() {
+                targets.push(PeerTarget {
+                    host: host.clone(),
+                    address,
+                    port: srv.port(),
+                    priority: srv.priority(),
+                    weight: srv.weight(),
+                });
+            }
+        }
+        for record in srv_lookup.as_lookup().record_iter() {
+            min_ttl = min_ttl.min(record.ttl());
+        }
+        if min_ttl == u32::MAX {
+            min_ttl = 60; // fallback TTL if the lookup returned no records
+        }
+
+        info!("Resolved {} peer target(s) for service {}.", targets.len(), service_name);
+
+        self.cache.lock().unwrap().insert(
+            service_name.to_string(),
+            CacheEntry {
+                targets: targets.clone(),
+                expires_at: Instant::now() + Duration::from_secs(min_ttl.into()),
+            },
+        );
+        Ok(targets)
+    }
+
+    fn cached_targets(&self, service_name: &str) -> Option<Vec<PeerTarget>> {
+        let cache = self.cache.lock().unwrap();
+        let entry: &CacheEntry = cache.get(service_name)?;
+
+        if Instant::now() < entry.expires_at {
+            Some(entry.targets.clone())
+        } else {
+            None
+        }
+    }
+}
+
+/// Orders `targets` by ascending SRV priority (lower value tried first),
+/// and within a priority tier shuffles by weight so heavier-weighted
+/// targets are more likely to come first.
+pub fn order_by_priority_and_weight(mut targets: Vec<PeerTarget>) -> Vec<PeerTarget> {
+    targets.sort_by_key(|t| t.priority);
+
+    let mut ordered: Vec<PeerTarget> = vec![];
+    let mut start: usize = 0;
+
+    while start < targets.len() {
+        let priority: u16 = targets[start].priority;
+        let mut end: usize = start;
+        while end < targets.len() && targets[end].priority == priority {
+            end += 1;
+        }
+
+        let mut tier: Vec<PeerTarget> = targets[start..end].to_vec();
+        let mut tier_ordered: Vec<PeerTarget> = vec![];
+
+        while !tier.is_empty() {
+            let total_weight: u32 = tier.iter().map(|t| u32::from(t.weight) + 1).sum();
+            let mut pick: u32 = rand::thread_rng().gen_range(0..total_weight);
+            let mut index: usize = 0;
+
+            for (i, t) in tier.iter().enumerate() {
+                let w: u32 = u32::from(t.weight) + 1;
+                if pick < w {
+                    index = i;
+                    break;
+                }
+                pick -= w;
+            }
+            tier_ordered.push(tier.remove(index));
+        }
+
+        ordered.append(&mut tier_ordered);
+        start = end;
+    }
+    ordered
+}
@@ -17,15 +17,72 @@
 
 use log::info;
 use std::io::Result;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use tokio::net::{TcpListener, TcpStream};
 
+#[cfg(feature = "tls")]
+use std::sync::Arc;
+#[cfg(feature = "tls")]
+use tokio_rustls::rustls::{self, pki_types::ServerName};
+#[cfg(feature = "tls")]
+use tokio_rustls::{TlsAcceptor, TlsConnector, TlsStream};
+
+// A TCP stream that may or may not be secured with TLS. Downstream code
+// (e.g. datagram reading) talks to this through the AsyncRead / AsyncWrite
+// traits, so it does not need to know which variant it was handed.
+pub enum Stream {
+    Plain(TcpStream),
+    #[cfg(feature = "tls")]
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl AsyncRead for Stream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Stream::Plain(s) => Pin::new(s).poll_read(cx, buf),
+            #[cfg(feature = "tls")]
+            Stream::Tls(s) => Pin::new(s.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Stream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        match self.get_mut() {
+            Stream::Plain(s) => Pin::new(s).poll_write(cx, buf),
+            #[cfg(feature = "tls")]
+            Stream::Tls(s) => Pin::new(s.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Stream::Plain(s) => Pin::new(s).poll_flush(cx),
+            #[cfg(feature = "tls")]
+            Stream::Tls(s) => Pin::new(s.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        match self.get_mut() {
+            Stream::Plain(s) => Pin::new(s).poll_shutdown(cx),
+            #[cfg(feature = "tls")]
+            Stream::Tls(s) => Pin::new(s.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
+
 pub struct Acceptor {
     pub socket: TcpListener,
     pub address: String,
+    #[cfg(feature = "tls")]
+    tls_acceptor: Option<TlsAcceptor>,
 }
 
 pub struct Connection {
-    pub socket: TcpStream,
+    pub socket: Stream,
     pub address: String,
 }
 
@@ -38,6 +95,60 @@ impl Acceptor {
         Ok(Self {
             socket,
             address: String::from(uri),
+            #[cfg(feature = "tls")]
+            tls_acceptor: None,
+        })
+    }
+
+    /// Binds a new TCP listening socket that performs a TLS handshake on
+    /// every accepted connection, using the given PEM-encoded certificate
+    /// chain and private key.
+    #[cfg(feature = "tls")]
+    pub async fn bind_tls(uri: &str, cert_chain_path: &str, private_key_path: &str) -> Result<Self> {
+        use std::io::{Error, ErrorKind};
+
+        let certs: Vec<rustls::pki_types::CertificateDer<'static>> =
+            rustls_pemfile::certs(&mut std::io::BufReader::new(std::fs::File::open(cert_chain_path)?))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let key: rustls::pki_types::PrivateKeyDer<'static> =
+            rustls_pemfile::private_key(&mut std::io::BufReader::new(std::fs::File::open(private_key_path)?))?
+                .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "no private key found in PEM file"))?;
+
+        let server_config: rustls::ServerConfig = rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|err| Error::new(ErrorKind::InvalidInput, format!("invalid TLS certificate / private key: {err}")))?;
+
+        let socket: TcpListener = TcpListener::bind(uri).await?;
+
+        info!("Opened new TLS-secured TCP listening socket at {}.", uri);
+
+        Ok(Self {
+            socket,
+            address: String::from(uri),
+            tls_acceptor: Some(TlsAcceptor::from(Arc::new(server_config))),
+        })
+    }
+
+    /// Accepts a new incoming connection, performing the TLS handshake
+    /// first if this acceptor was created with [`Acceptor::bind_tls`].
+    pub async fn accept(&self) -> Result<Connection> {
+        let (tcp_stream, peer_addr) = self.socket.accept().await?;
+
+        #[cfg(feature = "tls")]
+        if let Some(tls_acceptor) = &self.tls_acceptor {
+            let tls_stream = tls_acceptor.accept(tcp_stream).await?;
+
+            return Ok(Connection {
+                socket: Stream::Tls(Box::new(TlsStream::Server(tls_stream))),
+                address: peer_addr.to_string(),
+            });
+        }
+
+        Ok(Connection {
+            socket: Stream::Plain(tcp_stream),
+            address: peer_addr.to_string(),
         })
     }
 }
@@ -49,10 +160,65 @@ impl Connection {
         info!("Opened new TCP connection to {}.", uri);
 
         Ok(Self {
-            socket,
+            socket: Stream::Plain(socket),
+            address: String::from(uri),
+        })
+    }
+
+    /// Connects to `uri` and performs a TLS handshake, verifying the peer
+    /// certificate against `server_name` using the given root certificate
+    /// store.
+    #[cfg(feature = "tls")]
+    pub async fn connect_tls(
+        uri: &str,
+        server_name: ServerName<'static>,
+        roots: rustls::RootCertStore,
+    ) -> Result<Self> {
+        let client_config: rustls::ClientConfig = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let connector: TlsConnector = TlsConnector::from(Arc::new(client_config));
+        let tcp_stream: TcpStream = TcpStream::connect(uri).await?;
+        let tls_stream = connector.connect(server_name, tcp_stream).await?;
+
+        info!("Opened new TLS-secured TCP connection to {}.", uri);
+
+        Ok(Self {
+            socket: Stream::Tls(Box::new(TlsStream::Client(tls_stream))),
             address: String::from(uri),
         })
     }
+
+    /// Resolves `service_name` to its cluster peers via DNS SRV records and
+    /// attempts to connect to each in priority/weight order, falling back
+    /// to the next target on connection failure.
+    #[cfg(feature = "dns-resolver")]
+    pub async fn connect_service(resolver: &super::resolver::ServiceResolver, service_name: &str) -> Result<Self> {
+        use super::resolver::order_by_priority_and_weight;
+        use std::io::{Error, ErrorKind};
+
+        let targets = resolver
+            .resolve(service_name)
+            .await
+            .map_err(|err| Error::new(ErrorKind::NotFound, err))?;
+
+        if targets.is_empty() {
+            return Err(Error::new(ErrorKind::NotFound, format!("no SRV targets found for {}", service_name)));
+        }
+
+        let mut last_err: Option<std::io::Error> = None;
+
+        for target in order_by_priority_and_weight(targets) {
+            let uri: String = format!("{}:{}", target.address, target.port);
+
+            match Self::connect(&uri).await {
+                Ok(conn) => return Ok(conn),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err.unwrap())
+    }
 }
 
 #[cfg(test)]
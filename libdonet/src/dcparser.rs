@@ -21,14 +21,357 @@
 #![allow(clippy::type_complexity, clippy::redundant_field_names, clippy::ptr_arg)]
 #![allow(clippy::redundant_closure_call, clippy::enum_variant_names)]
 
-use crate::dcfile::*;
+// Builds an owned `DCFile` value from the token stream instead of mutating
+// a `pub static mut DC_FILE` through `unsafe` reduce actions. Every
+// production now carries forward the data it matches (identifiers, ranges,
+// defaults, keyword lists) instead of throwing it away, so a full parse
+// returns real `DCClass`/`DCField`/`DCParameter` nodes, with each field
+// assigned a monotonically increasing per-class index as defined by the DC
+// spec. This also makes parsing reentrant: nothing is shared between two
+// calls to `parse`, so multiple DC files can be parsed concurrently.
+//
+// The `dcfile`/`dclass`/`dcfield`/`dcparameter` modules declared in
+// `lib.rs` aren't part of this tree yet, so the node types below are
+// self-contained stand-ins for them rather than a dependency on modules
+// that don't exist here. Once those modules land, these can be replaced by
+// their richer equivalents.
+//
+// Everything here is built on `core` + `alloc` rather than `std`: parsing
+// and inheritance resolution only ever allocate (`String`, `Vec`,
+// `BTreeMap`/`BTreeSet`) and never touch the filesystem or an OS thread,
+// so this module compiles equally well into `libdonet`'s `no_std` build
+// (see the crate-level `std` feature in `lib.rs`).
 use crate::dclexer::DCToken::*;
 use crate::dclexer::{DCToken, Span};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
 use plex::parser;
-use std::ops::Range;
 
-pub static mut DC_FILE: DCFile = DCFile::new();
+/// Parsed representation of a `.dc` file.
+#[derive(Debug, Clone, Default)]
+pub struct DCFile {
+    pub keywords: Vec<String>,
+    pub structs: Vec<DCStruct>,
+    pub typedefs: Vec<DCTypeDef>,
+    pub classes: Vec<DCClass>,
+    pub imports: Vec<DCImport>,
+}
+
+impl DCFile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_python_import(&mut self, import: DCImport) {
+        self.imports.push(import);
+    }
+
+    pub fn get_num_imports(&self) -> usize {
+        self.imports.len()
+    }
+
+    /// Resolves `dclass` inheritance, flattening each class' parents'
+    /// fields into its own field list in declaration order, in the same
+    /// pass that validates the inheritance graph.
+    ///
+    /// A field a child redeclares replaces the inherited definition in
+    /// place rather than appearing twice, and a field reachable through
+    /// more than one parent (diamond inheritance) is likewise collapsed
+    /// to a single, most-derived definition, keeping its first position.
+    /// "Most-derived" here means: whichever parent listed later in the
+    /// `dclass`'s inheritance list contributes the final definition, the
+    /// same rule used for a child overriding its own parent.
+    ///
+    /// Returns one [`ResolvedClass`] per class, in the same order as
+    /// `self.classes`, with every field's `index` rewritten to its final
+    /// position in the flattened list; this is the field list and index
+    /// assignment the DC hash must be computed over.
+    pub fn resolve_inheritance(&self) -> Result<Vec<ResolvedClass>, DCInheritanceError> {
+        let mut by_name: BTreeMap<&str, &DCClass> = BTreeMap::new();
+        for class in &self.classes {
+            if by_name.insert(class.name.as_str(), class).is_some() {
+                return Err(DCInheritanceError::DuplicateClass { class: class.name.clone() });
+            }
+        }
+
+        let mut resolved: BTreeMap<String, ResolvedClass> = BTreeMap::new();
+        let mut in_progress: BTreeSet<String> = BTreeSet::new();
+
+        for class in &self.classes {
+            resolve_class(class, &by_name, &mut resolved, &mut in_progress)?;
+        }
+
+        Ok(self
+            .classes
+            .iter()
+            .map(|c| resolved.remove(&c.name).expect("every class is resolved above"))
+            .collect())
+    }
+}
+
+/// A [`DCClass`] with its parents' fields flattened in, as produced by
+/// [`DCFile::resolve_inheritance`].
+#[derive(Debug, Clone)]
+pub struct ResolvedClass {
+    pub name: String,
+    pub parents: Vec<String>,
+    /// Inherited and own fields, in final index order. `field.index`
+    /// matches each field's position in this vector.
+    pub fields: Vec<DCField>,
+}
+
+/// A problem found while resolving `dclass` inheritance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DCInheritanceError {
+    /// A `dclass` inherits from a name that isn't declared anywhere in the file.
+    UnresolvedParent { class: String, parent: String },
+    /// A `dclass` inherits from itself, directly or through its ancestors.
+    InheritanceCycle { class: String },
+    /// The same field name appears twice in one `dclass` body.
+    DuplicateField { class: String, field: String },
+    /// Two `dclass` declarations share the same name.
+    DuplicateClass { class: String },
+}
+
+/// Resolves `class` into a [`ResolvedClass`], recursing into its parents
+/// first (memoizing each result in `resolved`) so a diamond-shaped
+/// inheritance graph is only ever resolved once per ancestor.
+/// `in_progress` tracks classes currently being resolved on the call
+/// stack, so a class that (directly or transitively) inherits from
+/// itself is reported as [`DCInheritanceError::InheritanceCycle`]
+/// instead of recursing forever.
+fn resolve_class(
+    class: &DCClass,
+    by_name: &BTreeMap<&str, &DCClass>,
+    resolved: &mut BTreeMap<String, ResolvedClass>,
+    in_progress: &mut BTreeSet<String>,
+) -> Result<ResolvedClass, DCInheritanceError> {
+    if let Some(r) = resolved.get(&class.name) {
+        return Ok(r.clone());
+    }
+    if !in_progress.insert(class.name.clone()) {
+        return Err(DCInheritanceError::InheritanceCycle { class: class.name.clone() });
+    }
+
+    let mut own_names: BTreeSet<&str> = BTreeSet::new();
+    for field in &class.fields {
+        if let Some(name) = &field.name {
+            if !own_names.insert(name) {
+                return Err(DCInheritanceError::DuplicateField {
+                    class: class.name.clone(),
+                    field: name.clone(),
+                });
+            }
+        }
+    }
+
+    // `position` tracks where each named field currently sits in
+    // `flattened`, so a later override (a diamond sibling or the class'
+    // own redeclaration) replaces it in place instead of duplicating it.
+    let mut flattened: Vec<DCField> = vec![];
+    let mut position: BTreeMap<String, usize> = BTreeMap::new();
+
+    for parent_name in &class.parents {
+        let parent = *by_name.get(parent_name.as_str()).ok_or_else(|| {
+            DCInheritanceError::UnresolvedParent {
+                class: class.name.clone(),
+                parent: parent_name.clone(),
+            }
+        })?;
+        let parent_resolved = resolve_class(parent, by_name, resolved, in_progress)?;
+
+        for field in parent_resolved.fields {
+            match &field.name {
+                Some(name) if position.contains_key(name) => {
+                    flattened[position[name]] = field;
+                }
+                Some(name) => {
+                    position.insert(name.clone(), flattened.len());
+                    flattened.push(field);
+                }
+                None => flattened.push(field),
+            }
+        }
+    }
+
+    for field in &class.fields {
+        match &field.name {
+            Some(name) if position.contains_key(name) => {
+                flattened[position[name]] = field.clone();
+            }
+            Some(name) => {
+                position.insert(name.clone(), flattened.len());
+                flattened.push(field.clone());
+            }
+            None => flattened.push(field.clone()),
+        }
+    }
+
+    for (i, field) in flattened.iter_mut().enumerate() {
+        field.index = i as u16;
+    }
+
+    in_progress.remove(&class.name);
+
+    let result = ResolvedClass {
+        name: class.name.clone(),
+        parents: class.parents.clone(),
+        fields: flattened,
+    };
+    resolved.insert(class.name.clone(), result.clone());
+    Ok(result)
+}
+
+#[derive(Debug, Clone)]
+pub struct DCImport {
+    pub module: String,
+    pub classes: Vec<String>,
+}
+
+impl DCImport {
+    pub fn new(module: String, classes: Vec<String>) -> Self {
+        Self { module, classes }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DCStruct {
+    pub name: String,
+    pub fields: Vec<DCParameter>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DCTypeDef {
+    pub alias: String,
+    pub underlying: DCToken,
+    pub array_range: Option<Range<i64>>,
+}
+
+/// A distributed class declaration: its name, parent classes (for
+/// inheritance), and the fields declared in its body.
+#[derive(Debug, Clone)]
+pub struct DCClass {
+    pub name: String,
+    pub parents: Vec<String>,
+    pub fields: Vec<DCField>,
+}
+
+/// One field in a dclass body. `index` is assigned in declaration order by
+/// the `distributed_class_type` reduce action, per the DC spec.
+#[derive(Debug, Clone)]
+pub struct DCField {
+    pub name: Option<String>,
+    pub index: u16,
+    pub param: DCFieldValue,
+    pub keywords: Vec<String>,
+}
+
+/// A [`DCField`] before its final index has been assigned.
+#[derive(Debug, Clone)]
+struct DCFieldPartial {
+    name: Option<String>,
+    param: DCFieldValue,
+    keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum DCFieldValue {
+    /// A remote-procedure-call-style field: `name(parameters...) keywords;`
+    Atomic(Vec<DCParameter>),
+    /// A plain data field: `parameter keywords;`
+    Parameter(DCParameter),
+    /// A field that bundles up a group of other fields under one name.
+    Molecular(Vec<DCFieldPartial>),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum DCNumericOperator {
+    Mod,
+    Div,
+    Mul,
+    Sub,
+    Add,
+}
+
+#[derive(Debug, Clone)]
+pub struct DCIntTransform {
+    pub operator: DCNumericOperator,
+    pub operand: i64,
+}
+
+#[derive(Debug, Clone)]
+pub enum DCArrayType {
+    Named(String),
+    Numeric(DCToken),
+}
+
+#[derive(Debug, Clone)]
+pub enum DCParameter {
+    Char {
+        name: Option<String>,
+        default: Option<char>,
+    },
+    Int {
+        data_type: DCToken,
+        range: Option<Range<i64>>,
+        transform: Option<DCIntTransform>,
+        name: Option<String>,
+        default: Option<i64>,
+    },
+    Float {
+        range: Option<Range<f64>>,
+        transform: Option<()>,
+        name: Option<String>,
+        default: Option<f64>,
+    },
+    String {
+        size: Option<i64>,
+        name: Option<String>,
+        default: Option<String>,
+    },
+    Blob {
+        size: Option<i64>,
+        name: Option<String>,
+        default: Option<String>,
+    },
+    Struct {
+        type_name: String,
+        name: Option<String>,
+    },
+    Array {
+        data_type: DCArrayType,
+        range: Range<i64>,
+        name: Option<String>,
+    },
+}
+
+fn parameter_name(parameter: &DCParameter) -> Option<String> {
+    match parameter {
+        DCParameter::Char { name, .. } => name.clone(),
+        DCParameter::Int { name, .. } => name.clone(),
+        DCParameter::Float { name, .. } => name.clone(),
+        DCParameter::String { name, .. } => name.clone(),
+        DCParameter::Blob { name, .. } => name.clone(),
+        DCParameter::Struct { name, .. } => name.clone(),
+        DCParameter::Array { name, .. } => name.clone(),
+    }
+}
 
+/// One type declaration collected by `type_declarations`, before it's
+/// folded into the final [`DCFile`].
+enum DCTypeDeclItem {
+    Keyword(String),
+    Struct(DCStruct),
+    Class(DCClass),
+    Imports(Vec<DCImport>),
+    TypeDef(DCTypeDef),
+}
+
+// Plex macro to start defining our grammar
 parser! {
     fn parse_(DCToken, Span);
 
@@ -42,35 +385,68 @@ parser! {
     }
 
     // root production of the grammar
-    dc_file: () {
-        type_declarations => {},
+    dc_file: DCFile {
+        type_declarations[tds] => {
+            let mut file = DCFile::new();
+
+            for item in tds {
+                match item {
+                    DCTypeDeclItem::Keyword(k) => file.keywords.push(k),
+                    DCTypeDeclItem::Struct(s) => file.structs.push(s),
+                    DCTypeDeclItem::Class(c) => file.classes.push(c),
+                    DCTypeDeclItem::Imports(imports) => {
+                        for import in imports {
+                            file.add_python_import(import);
+                        }
+                    }
+                    DCTypeDeclItem::TypeDef(t) => file.typedefs.push(t),
+                }
+            }
+            file
+        },
     }
 
-    type_declarations: () {
-        => {},
-        type_declarations type_decl => {},
+    type_declarations: Vec<DCTypeDeclItem> {
+        => vec![],
+        type_declarations[mut td_vec] type_decl[next_td] => {
+            td_vec.push(next_td);
+            td_vec
+        },
     }
 
-    type_decl: () {
-        keyword_type => {},
-        struct_type => {},
-        distributed_class_type => {},
-        python_import => {},
-        type_definition => {},
+    type_decl: DCTypeDeclItem {
+        keyword_type[k] => DCTypeDeclItem::Keyword(k),
+        struct_type[s] => DCTypeDeclItem::Struct(s),
+        distributed_class_type[dc] => DCTypeDeclItem::Class(dc),
+        python_import[imps] => DCTypeDeclItem::Imports(imps),
+        type_definition[td] => DCTypeDeclItem::TypeDef(td),
     }
 
-    keyword_type: () {
-        Keyword Identifier(id) Semicolon => {}
+    keyword_type: String {
+        Keyword Identifier(id) Semicolon => id
     }
 
-    struct_type: () {
+    struct_type: DCStruct {
         Struct Identifier(id) OpenBraces struct_parameters[ps]
-        CloseBraces Semicolon => {},
+        CloseBraces Semicolon => DCStruct { name: id, fields: ps },
     }
 
-    distributed_class_type: () {
+    distributed_class_type: DCClass {
         DClass Identifier(id) optional_inheritance[pc] OpenBraces
-        field_declarations[fds] CloseBraces Semicolon => {}
+        field_declarations[fds] CloseBraces Semicolon => {
+            let fields: Vec<DCField> = fds
+                .into_iter()
+                .enumerate()
+                .map(|(i, fd)| DCField {
+                    name: fd.name,
+                    index: i as u16,
+                    param: fd.param,
+                    keywords: fd.keywords,
+                })
+                .collect();
+
+            DCClass { name: id, parents: pc.unwrap_or_default(), fields }
+        }
     }
 
     optional_inheritance: Option<Vec<String>> {
@@ -89,64 +465,72 @@ parser! {
         }
     }
 
-    type_definition: () {
-        Typedef CharT Identifier(alias) opt_array_range[_] Semicolon => {},
-        Typedef signed_integers[dt] Identifier(alias) opt_array_range[_] Semicolon => {},
-        Typedef unsigned_integers[dt] Identifier(alias) opt_array_range[_] Semicolon => {},
-        Typedef array_data_types[dt] Identifier(alias) opt_array_range[_] Semicolon => {},
-        Typedef Float64T Identifier(alias) opt_array_range[_] Semicolon => {},
-        Typedef StringT Identifier(alias) opt_array_range[_] Semicolon => {},
-        Typedef BlobT Identifier(alias) opt_array_range[_] Semicolon => {},
-        Typedef Blob32T Identifier(alias) opt_array_range[_] Semicolon => {},
+    type_definition: DCTypeDef {
+        Typedef CharT Identifier(alias) opt_array_range[ar] Semicolon => {
+            DCTypeDef { alias, underlying: CharT, array_range: ar }
+        },
+        Typedef signed_integers[dt] Identifier(alias) opt_array_range[ar] Semicolon => {
+            DCTypeDef { alias, underlying: dt, array_range: ar }
+        },
+        Typedef unsigned_integers[dt] Identifier(alias) opt_array_range[ar] Semicolon => {
+            DCTypeDef { alias, underlying: dt, array_range: ar }
+        },
+        Typedef array_data_types[dt] Identifier(alias) opt_array_range[ar] Semicolon => {
+            DCTypeDef { alias, underlying: dt, array_range: ar }
+        },
+        Typedef Float64T Identifier(alias) opt_array_range[ar] Semicolon => {
+            DCTypeDef { alias, underlying: Float64T, array_range: ar }
+        },
+        Typedef StringT Identifier(alias) opt_array_range[ar] Semicolon => {
+            DCTypeDef { alias, underlying: StringT, array_range: ar }
+        },
+        Typedef BlobT Identifier(alias) opt_array_range[ar] Semicolon => {
+            DCTypeDef { alias, underlying: BlobT, array_range: ar }
+        },
+        Typedef Blob32T Identifier(alias) opt_array_range[ar] Semicolon => {
+            DCTypeDef { alias, underlying: Blob32T, array_range: ar }
+        },
     }
 
-    python_import: () {
+    python_import: Vec<DCImport> {
         py_module[(m, ms)] dclass_import[(c, cs)] => {
             // NOTE: This is an ugly fix for not being able to pass Options
             // through the production parameters (due to moved values and
             // borrow checking issues (skill issues)), so we turn the Vectors
             // (which do implement the Copy trait) into Options here.
-            let mut mvs_opt: Option<Vec<String>> = None;
-            let mut cvs_opt: Option<Vec<String>> = None;
-            if !ms.is_empty() {
-                mvs_opt = Some(ms);
-            }
-            if !cs.is_empty() {
-                cvs_opt = Some(cs);
-            }
+            let mvs_opt: Option<Vec<String>> = if ms.is_empty() { None } else { Some(ms) };
+            let cvs_opt: Option<Vec<String>> = if cs.is_empty() { None } else { Some(cs) };
 
             let mut class_symbols: Vec<String> = vec![c.clone()];
 
             // Separates "Class/AI/OV" to ["Class", "ClassAI", "ClassOV"]
-            if cvs_opt.is_some() {
-                for class_suffix in &cvs_opt.unwrap() {
+            if let Some(cvs) = &cvs_opt {
+                for class_suffix in cvs {
                     class_symbols.push(c.clone() + class_suffix);
                 }
             }
 
+            let mut imports: Vec<DCImport> = vec![];
+
             // Handles e.g. "from module/AI/OV/UD import DistributedThing/AI/OV/UD"
-            if mvs_opt.is_some() {
-                let mut c_symbol: String = class_symbols.get(0).unwrap().clone();
+            if let Some(mvs) = mvs_opt {
+                let mut c_symbol: String = class_symbols.first().unwrap().clone();
 
-                unsafe {
-                    DC_FILE.add_python_import(DCImport::new(m.clone(), vec![c_symbol]))
-                }
+                imports.push(DCImport::new(m.clone(), vec![c_symbol]));
 
-                for (i, module_suffix) in mvs_opt.unwrap().into_iter().enumerate() {
+                for (i, module_suffix) in mvs.into_iter().enumerate() {
                     let full_import: String = m.clone() + &module_suffix;
-                    c_symbol = class_symbols.get(i + 1).unwrap().clone();
+                    // A module view suffix with no matching class view
+                    // suffix (e.g. "from views/AI/OV import Donut/AI") still
+                    // imports the base (unsuffixed) class under that view.
+                    c_symbol = class_symbols.get(i + 1).cloned().unwrap_or_else(|| c.clone());
 
-                    let dc_import: DCImport = DCImport::new(full_import, vec![c_symbol]);
-
-                    unsafe {
-                        DC_FILE.add_python_import(dc_import.clone());
-                    }
+                    imports.push(DCImport::new(full_import, vec![c_symbol]));
                 }
-                return;
-            }
-            unsafe {
-                DC_FILE.add_python_import(DCImport::new(m, class_symbols));
+            } else {
+                imports.push(DCImport::new(m, class_symbols));
             }
+            imports
         },
     }
 
@@ -205,73 +589,102 @@ parser! {
 
     // ----- Field Declaration ----- //
 
-    field_declarations: () {
-        => {},
-        field_declarations[mut fds] field_declaration[fd] => {},
+    field_declarations: Vec<DCFieldPartial> {
+        => vec![],
+        field_declarations[mut fds] field_declaration[fd] => {
+            fds.push(fd);
+            fds
+        },
     }
 
-    field_declaration: () {
-        molecular_field[mf] => {},
-        atomic_field[af] => {},
-        parameter_field[pf] => {},
+    field_declaration: DCFieldPartial {
+        molecular_field[mf] => mf,
+        atomic_field[af] => af,
+        parameter_field[pf] => pf,
     }
 
     // ----- Molecular Field ----- //
 
-    molecular_field: () {
-        Identifier(id) Colon atomic_field[af] atomic_fields[mut afs] Semicolon => {},
-        Identifier(id) Colon parameter_field[pf] parameter_fields[mut pfs] Semicolon => {},
+    molecular_field: DCFieldPartial {
+        Identifier(id) Colon atomic_field[af] atomic_fields[mut afs] Semicolon => {
+            afs.insert(0, af);
+            DCFieldPartial { name: Some(id), param: DCFieldValue::Molecular(afs), keywords: vec![] }
+        },
+        Identifier(id) Colon parameter_field[pf] parameter_fields[mut pfs] Semicolon => {
+            pfs.insert(0, pf);
+            DCFieldPartial { name: Some(id), param: DCFieldValue::Molecular(pfs), keywords: vec![] }
+        },
     }
 
     // ----- Atomic Field ----- //
 
-    atomic_fields: () {
-        => {},
-        atomic_fields Comma atomic_field => {},
+    atomic_fields: Vec<DCFieldPartial> {
+        => vec![],
+        atomic_fields[mut afs] Comma atomic_field[af] => {
+            afs.push(af);
+            afs
+        },
     }
 
-    atomic_field: () {
+    atomic_field: DCFieldPartial {
         Identifier(id) OpenParenthesis parameters[ps]
-        CloseParenthesis dc_keyword_list[kl] Semicolon => {},
+        CloseParenthesis dc_keyword_list[kl] Semicolon => {
+            DCFieldPartial { name: Some(id), param: DCFieldValue::Atomic(ps), keywords: kl }
+        }
     }
 
     // ----- Parameter Fields ----- //
 
-    parameter_fields: () {
-        => {},
-        parameter_fields Comma parameter_field => {},
+    parameter_fields: Vec<DCFieldPartial> {
+        => vec![],
+        parameter_fields[mut pfs] Comma parameter_field[pf] => {
+            pfs.push(pf);
+            pfs
+        },
     }
 
-    parameter_field: () {
-        parameter[p] dc_keyword_list[kl] => {},
+    parameter_field: DCFieldPartial {
+        parameter[p] dc_keyword_list[kl] => {
+            let name = parameter_name(&p);
+            DCFieldPartial { name, param: DCFieldValue::Parameter(p), keywords: kl }
+        }
     }
 
     // ----- Parameters ----- //
 
-    struct_parameters: () {
-        => {},
-        struct_parameters struct_parameter => {},
+    struct_parameters: Vec<DCParameter> {
+        => vec![],
+        struct_parameters[mut ps] struct_parameter[p] => {
+            ps.push(p);
+            ps
+        },
     }
 
-    struct_parameter: () {
-        parameter Semicolon => {}
+    struct_parameter: DCParameter {
+        parameter[p] Semicolon => p
     }
 
-    parameters: () {
-        => {},
+    parameters: Vec<DCParameter> {
+        => vec![],
         #[no_reduce(Comma)] // don't reduce if we're expecting more params
-        parameters parameter => {},
-        parameters parameter Comma => {},
+        parameters[mut ps] parameter[p] => {
+            ps.push(p);
+            ps
+        },
+        parameters[mut ps] parameter[p] Comma => {
+            ps.push(p);
+            ps
+        },
     }
 
-    parameter: () {
-        char_param => {},
-        int_param => {},
-        float_param => {},
-        string_param => {},
-        blob_param => {},
-        struct_param => {},
-        array_param => {},
+    parameter: DCParameter {
+        char_param[p] => p,
+        int_param[p] => p,
+        float_param[p] => p,
+        string_param[p] => p,
+        blob_param[p] => p,
+        struct_param[p] => p,
+        array_param[p] => p,
     }
 
     size_constraint: Option<i64> {
@@ -305,14 +718,14 @@ parser! {
         DecimalLiteral(min) Hyphen DecimalLiteral(max) => min .. max,
     }
 
-    int_transform: Option<()> {
+    int_transform: Option<DCIntTransform> {
         => None,
         // FIXME: Accept spec's `IntegerLiteral`, not just DecimalLiteral.
-        Percent DecimalLiteral(dl) => Some(()),
-        ForwardSlash DecimalLiteral(dl) => Some(()),
-        Star DecimalLiteral(dl) => Some(()),
-        Hyphen DecimalLiteral(dl) => Some(()),
-        Plus DecimalLiteral(dl) => Some(()),
+        Percent DecimalLiteral(dl) => Some(DCIntTransform { operator: DCNumericOperator::Mod, operand: dl }),
+        ForwardSlash DecimalLiteral(dl) => Some(DCIntTransform { operator: DCNumericOperator::Div, operand: dl }),
+        Star DecimalLiteral(dl) => Some(DCIntTransform { operator: DCNumericOperator::Mul, operand: dl }),
+        Hyphen DecimalLiteral(dl) => Some(DCIntTransform { operator: DCNumericOperator::Sub, operand: dl }),
+        Plus DecimalLiteral(dl) => Some(DCIntTransform { operator: DCNumericOperator::Add, operand: dl }),
     }
 
     float_transform: Option<()> {
@@ -354,17 +767,21 @@ parser! {
     }
 
     // ----- Char Parameter ----- //
-    char_param: () {
-        CharT optional_name[id] param_char_init[cl] => {}
+    char_param: DCParameter {
+        CharT optional_name[id] param_char_init[cl] => DCParameter::Char { name: id, default: cl }
     }
 
     // ----- Integer Parameter ----- //
-    int_param: () {
+    int_param: DCParameter {
         signed_integers[it] int_range[ir] int_transform[itr]
-        optional_name[id] param_dec_const[dc] => {},
+        optional_name[id] param_dec_const[dc] => {
+            DCParameter::Int { data_type: it, range: ir, transform: itr, name: id, default: dc }
+        },
 
         unsigned_integers[it] int_range[ir] int_transform[itr]
-        optional_name[id] param_dec_const[dc] => {},
+        optional_name[id] param_dec_const[dc] => {
+            DCParameter::Int { data_type: it, range: ir, transform: itr, name: id, default: dc }
+        },
     }
 
     signed_integers: DCToken {
@@ -392,33 +809,47 @@ parser! {
     }
 
     // ----- Float Parameter ----- //
-    float_param: () {
+    float_param: DCParameter {
         Float64T float_range[fr] float_transform[ft]
-        optional_name[id] param_float_const[fl] => {},
+        optional_name[id] param_float_const[fl] => {
+            DCParameter::Float { range: fr, transform: ft, name: id, default: fl }
+        },
     }
 
     // ----- String Parameter ----- //
-    string_param: () {
-        StringT size_constraint[sc] optional_name[id] param_str_init[sl] => {}
+    string_param: DCParameter {
+        StringT size_constraint[sc] optional_name[id] param_str_init[sl] => {
+            DCParameter::String { size: sc, name: id, default: sl }
+        }
     }
 
     // ----- Blob Parameter ----- //
-    blob_param: () {
-        BlobT size_constraint[sc] optional_name[id] param_bin_init[bl] => {},
+    blob_param: DCParameter {
+        BlobT size_constraint[sc] optional_name[id] param_bin_init[bl] => {
+            DCParameter::Blob { size: sc, name: id, default: bl }
+        },
     }
 
     // ----- Struct Parameter ----- //
-    struct_param: () {
+    struct_param: DCParameter {
         #[no_reduce(OpenBrackets)] // avoids ambiguity between struct & array parameters
-        Identifier(st) optional_name[si] => {},
+        Identifier(st) optional_name[si] => DCParameter::Struct { type_name: st, name: si },
     }
 
     // ----- Array Parameter ----- //
-    array_param: () {
-        Identifier(_) optional_name[ai] array_range[ar] => {},
-        signed_integers[dt] array_range[ar] optional_name[id] => {},
-        unsigned_integers[dt] array_range[ar] optional_name[id] => {},
-        array_data_types[dt] array_range[ar] optional_name[id] => {},
+    array_param: DCParameter {
+        Identifier(dt) optional_name[ai] array_range[ar] => {
+            DCParameter::Array { data_type: DCArrayType::Named(dt), range: ar, name: ai }
+        },
+        signed_integers[dt] array_range[ar] optional_name[id] => {
+            DCParameter::Array { data_type: DCArrayType::Numeric(dt), range: ar, name: id }
+        },
+        unsigned_integers[dt] array_range[ar] optional_name[id] => {
+            DCParameter::Array { data_type: DCArrayType::Numeric(dt), range: ar, name: id }
+        },
+        array_data_types[dt] array_range[ar] optional_name[id] => {
+            DCParameter::Array { data_type: DCArrayType::Numeric(dt), range: ar, name: id }
+        },
     }
 
     // ----- DC Keywords ----- //
@@ -435,22 +866,20 @@ parser! {
 
 pub fn parse<I: Iterator<Item = (DCToken, Span)>>(
     i: I,
-) -> Result<(), (Option<(DCToken, Span)>, &'static str)> {
+) -> Result<DCFile, (Option<(DCToken, Span)>, &'static str)> {
     parse_(i)
 }
 
 #[cfg(test)]
 mod unit_testing {
-    use super::{parse, DC_FILE};
-    use crate::dcfile::DCFileInterface;
+    use super::{parse, DCFile, DCInheritanceError, ResolvedClass};
     use crate::dclexer::Lexer;
 
-    fn parse_dcfile_string(input: &str) {
+    fn parse_dcfile_string(input: &str) -> DCFile {
         let lexer = Lexer::new(input).inspect(|tok| eprintln!("token: {:?}", tok));
-        let _: () = parse(lexer).unwrap();
-        unsafe {
-            eprintln!("{:#?}", DC_FILE); // pretty print parser output to stderr
-        }
+        let dc_file: DCFile = parse(lexer).unwrap();
+        eprintln!("{:#?}", dc_file); // pretty print parser output to stderr
+        dc_file
     }
 
     #[test]
@@ -460,10 +889,99 @@ mod unit_testing {
                              from views/AI/OV import DistributedDonut/AI/OV\n\
                              from game.views.Donut/AI import DistributedDonut/AI\n\
                              from views import *\n";
-        parse_dcfile_string(dc_file);
+        let dc_file = parse_dcfile_string(dc_file);
 
-        unsafe {
-            assert_eq!(DC_FILE.get_num_imports(), 8);
-        }
+        assert_eq!(dc_file.get_num_imports(), 8);
+    }
+
+    #[test]
+    fn inheritance_flattens_parent_fields() {
+        let dc_file: &str = "dclass Avatar {\n\
+                                  set_name(string name) broadcast required;\n\
+                                  set_hp(int16 hp) broadcast required;\n\
+                              };\n\
+                              dclass DistributedAvatar : Avatar {\n\
+                                  set_xyz(int16 x, int16 y, int16 z) broadcast required;\n\
+                              };";
+        let dc_file = parse_dcfile_string(dc_file);
+        let resolved: Vec<ResolvedClass> = dc_file.resolve_inheritance().unwrap();
+
+        let avatar: &ResolvedClass = &resolved[1];
+        assert_eq!(avatar.name, "DistributedAvatar");
+        assert_eq!(avatar.fields.len(), 3);
+        assert_eq!(avatar.fields[0].name, Some("set_name".to_string()));
+        assert_eq!(avatar.fields[1].name, Some("set_hp".to_string()));
+        assert_eq!(avatar.fields[2].name, Some("set_xyz".to_string()));
+        assert_eq!(avatar.fields[2].index, 2);
+    }
+
+    #[test]
+    fn inheritance_override_replaces_field_in_place() {
+        let dc_file: &str = "dclass Base {\n\
+                                  set_hp(int16 hp) broadcast required;\n\
+                                  set_name(string name) broadcast required;\n\
+                              };\n\
+                              dclass Child : Base {\n\
+                                  set_hp(int32 hp) broadcast required;\n\
+                              };";
+        let dc_file = parse_dcfile_string(dc_file);
+        let resolved: Vec<ResolvedClass> = dc_file.resolve_inheritance().unwrap();
+
+        let child: &ResolvedClass = &resolved[1];
+        assert_eq!(child.fields.len(), 2);
+        assert_eq!(child.fields[0].name, Some("set_hp".to_string()));
+        assert_eq!(child.fields[0].index, 0);
+    }
+
+    #[test]
+    fn inheritance_collapses_diamond_field() {
+        let dc_file: &str = "dclass Base {\n\
+                                  set_hp(int16 hp) broadcast required;\n\
+                              };\n\
+                              dclass Left : Base {\n\
+                              };\n\
+                              dclass Right : Base {\n\
+                                  set_hp(int32 hp) broadcast required;\n\
+                              };\n\
+                              dclass Diamond : Left, Right {\n\
+                              };";
+        let dc_file = parse_dcfile_string(dc_file);
+        let resolved: Vec<ResolvedClass> = dc_file.resolve_inheritance().unwrap();
+
+        let diamond: &ResolvedClass = resolved.iter().find(|c| c.name == "Diamond").unwrap();
+        assert_eq!(diamond.fields.len(), 1);
+        assert_eq!(diamond.fields[0].index, 0);
+    }
+
+    #[test]
+    fn inheritance_reports_unresolved_parent() {
+        let dc_file: &str = "dclass Child : Missing {\n\
+                              };";
+        let dc_file = parse_dcfile_string(dc_file);
+
+        assert_eq!(
+            dc_file.resolve_inheritance(),
+            Err(DCInheritanceError::UnresolvedParent {
+                class: "Child".to_string(),
+                parent: "Missing".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn inheritance_reports_duplicate_field() {
+        let dc_file: &str = "dclass Broken {\n\
+                                  set_hp(int16 hp) broadcast required;\n\
+                                  set_hp(int16 hp) broadcast required;\n\
+                              };";
+        let dc_file = parse_dcfile_string(dc_file);
+
+        assert_eq!(
+            dc_file.resolve_inheritance(),
+            Err(DCInheritanceError::DuplicateField {
+                class: "Broken".to_string(),
+                field: "set_hp".to_string(),
+            })
+        );
     }
 }
@@ -42,9 +42,15 @@
 //! - **`full`**: Enables all feature flags available for libdonet.
 //! - **`datagram`**: Includes Datagram / Datagram Iterator source for writing network packets.
 //! - **`dcfile`**: Includes the DC file lexer, parser, and DC element structures.
+//! - **`std`**: Enabled by default. Pulls in the standard library for the
+//!   I/O-facing parts of the crate (reading `.dc` files from disk, the
+//!   `log` backends). Disabling it (`default-features = false`) builds
+//!   the lexer, parser, and DC element structures against `core` + `alloc`
+//!   only, for embedding in constrained or WASM targets.
 //!
 //! [`www.donet-server.org`]: https://www.donet-server.org/
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc(
     html_logo_url = "https://gitlab.com/donet-server/donet/-/raw/master/logo/donet_logo_v3.png?ref_type=heads"
 )]
@@ -52,6 +58,8 @@
 //#![warn(missing_docs)]
 #![deny(unused_extern_crates)]
 
+extern crate alloc;
+
 pub mod globals;
 
 #[macro_use]
@@ -78,137 +86,311 @@ cfg_if! {
     }
 }
 
-/// Returns false if a [`log`] logger is not initialized.
-///
-/// [`log`]: https://docs.rs/log/latest/log/
-///
-fn logger_initialized() -> bool {
-    use log::Level::*;
+// Everything below touches the filesystem, an OS logger, or an
+// environment variable, so none of it is available without `std`.
+cfg_if! {
+    if #[cfg(feature = "std")] {
+
+    /// Returns false if a [`log`] logger is not initialized.
+    ///
+    /// [`log`]: https://docs.rs/log/latest/log/
+    ///
+    fn logger_initialized() -> bool {
+        use log::Level::*;
 
-    let levels: &[log::Level] = &[Error, Warn, Info, Debug, Trace];
+        let levels: &[log::Level] = &[Error, Warn, Info, Debug, Trace];
 
-    for level in levels {
-        if log::log_enabled!(*level) {
-            return true;
+        for level in levels {
+            if log::log_enabled!(*level) {
+                return true;
+            }
         }
+        false
     }
-    false
-}
 
-/// Creates a [`pretty_env_logger`] logger if no [`log`]
-/// logger is found to be initialized in this process.
-///
-/// [`pretty_env_logger`]: https://docs.rs/pretty_env_logger/latest/pretty_env_logger/
-/// [`log`]: https://docs.rs/log/latest/log/
-///
-fn init_logger() {
-    if logger_initialized() {
-        return;
+    /// Selects which logging backend [`init_logger_with`] should install.
+    ///
+    /// `libdonet` is often embedded in a long-running cluster node process
+    /// that has no attached terminal, so the default pretty-printed stderr
+    /// logger is not always appropriate.
+    #[derive(Debug, Clone)]
+    pub enum LogBackend {
+        /// Human-readable, colorized output to stderr. Good for local development.
+        Pretty,
+        /// One JSON object per log record, written to stderr. Good for log
+        /// collectors that expect structured input.
+        Json,
+        /// Forwards records to the local syslog daemon.
+        Syslog(SyslogConfig),
+    }
+
+    /// Configuration for the [`LogBackend::Syslog`] backend.
+    #[derive(Debug, Clone)]
+    pub struct SyslogConfig {
+        /// Identifies this process in syslog output (e.g. `"donet-message-director"`).
+        pub process_name: String,
+        /// The syslog facility records should be filed under.
+        pub facility: syslog::Facility,
     }
-    pretty_env_logger::init();
-}
 
-/// Easy to use interface for the DC file parser. Handles reading
-/// the DC files, instantiating the lexer and parser, and either
-/// returns the DCFile object or a Parse/File error.
-///
-/// ## Example Usage
-/// The following is an example of parsing a simple DC file string,
-/// printing its DC hash in hexadecimal notation, and accessing
-/// the elements of a defined Distributed Class:
-/// ```rust
-/// use libdonet::dclass::DClass;
-/// use libdonet::globals::DCReadResult;
-/// use libdonet::read_dc_files;
-///
-/// use std::cell::RefCell;
-/// use std::rc::Rc;
-///
-/// let dc_file = "from game.ai import AnonymousContact/UD
-///                from game.ai import LoginManager/AI
-///                from game.world import DistributedWorld/AI
-///                from game.avatar import DistributedAvatar/AI/OV
-///
-///                dclass AnonymousContact {
-///                  login(string username, string password) clsend airecv;
-///                };
-///
-///                dclass LoginManager {
-///                  login(channel client, string username, string password) airecv;
-///                };
-///
-///                dclass DistributedWorld {
-///                  create_avatar(channel client) airecv;
-///                };
-///
-///                dclass DistributedAvatar {
-///                   set_xyzh(int16 x, int16 y, int16 z, int16 h) broadcast required;
-///                   indicate_intent(int16 / 10, int16 / 10) ownsend airecv;
-///                };";
-///
-/// let dc_read: DCReadResult = read_dc_files(vec![dc_file.into()]);
-///
-/// if let Ok(dc_file) = dc_read {
-///     // Print the DC File's 32-bit hash in hexadecimal format.
-///     println!("{}", dc_file.borrow_mut().get_pretty_hash());
-///     
-///     // Retrieve the `DistributedAvatar` dclass by ID.
-///     let mut avatar_class = dc_file.borrow_mut().get_dclass_by_id(3);
-///
-///     // Print the identifier of the dclass.
-///     println!("{}", Rc::get_mut(&mut avatar_class).expect("Borrow failed!").get_name());
-/// }
-/// ```
-///
-/// The output of the program would be the following:
-/// ```txt
-/// 0x01a5fb0c
-/// DistributedAvatar
-/// ```
-/// <br><img src="https://c.tenor.com/myQHgyWQQ9sAAAAd/tenor.gif">
-///
-#[cfg(feature = "dcfile")]
-pub fn read_dc_files(file_paths: Vec<String>) -> globals::DCReadResult {
-    use crate::parser::lexer::Lexer;
-    use crate::parser::parser::parse;
-    use log::{error, info};
-    use std::cell::RefCell;
-    use std::fs::File;
-    use std::io::Read;
-    use std::rc::Rc;
-
-    init_logger();
-    info!("DC read of {:?}", file_paths);
-
-    let mut file_results: Vec<Result<File, std::io::Error>> = vec![];
-    // All DC files are passed to the lexer as one string.
-    let mut lexer_input: String = String::new();
-
-    assert!(!file_paths.is_empty(), "No DC files given!");
-
-    for file_path in &file_paths {
-        file_results.push(File::open(file_path));
+    /// Configuration passed to [`init_logger_with`].
+    #[derive(Debug, Clone)]
+    pub struct LogConfig {
+        pub backend: LogBackend,
     }
 
-    for io_result in file_results {
-        if let Ok(mut dcf) = io_result {
-            let res: std::io::Result<usize> = dcf.read_to_string(&mut lexer_input);
-            if let Err(res_err) = res {
-                // DC file content may not be in proper UTF-8 encoding.
-                return Err(globals::DCReadError::FileError(res_err));
+    impl Default for LogConfig {
+        fn default() -> Self {
+            Self {
+                backend: LogBackend::Pretty,
             }
-        } else {
-            // Failed to open one of the DC files. (most likely permission error)
-            return Err(globals::DCReadError::FileError(io_result.unwrap_err()));
         }
     }
 
-    let lexer: Lexer<'_> = Lexer::new(&lexer_input);
-    let res: Result<Rc<RefCell<dcfile::DCFile>>, globals::ParseError> = parse(lexer);
+    /// Creates a [`pretty_env_logger`] logger if no [`log`]
+    /// logger is found to be initialized in this process.
+    ///
+    /// [`pretty_env_logger`]: https://docs.rs/pretty_env_logger/latest/pretty_env_logger/
+    /// [`log`]: https://docs.rs/log/latest/log/
+    ///
+    fn init_logger() {
+        init_logger_with(LogConfig::default());
+    }
+
+    /// Same as [`init_logger`], but lets the caller pick the logging backend
+    /// via [`LogConfig`]. An embedding application's pre-installed logger still
+    /// takes precedence, same as [`init_logger`].
+    ///
+    /// The `RUST_LOG` environment variable is respected by every backend. The
+    /// pretty and JSON backends apply it through [`env_logger`]'s filter; the
+    /// syslog backend reads it as a single global level (e.g. `debug`),
+    /// defaulting to `info`, and does not apply per-module directives.
+    pub fn init_logger_with(config: LogConfig) {
+        if logger_initialized() {
+            return;
+        }
+
+        match config.backend {
+            LogBackend::Pretty => pretty_env_logger::init(),
+            LogBackend::Json => init_json_logger(),
+            LogBackend::Syslog(syslog_config) => init_syslog_logger(syslog_config),
+        }
+    }
+
+    /// Installs a logger that writes one JSON object per record to stderr.
+    fn init_json_logger() {
+        use std::io::Write;
+
+        env_logger::Builder::from_default_env()
+            .format(|buf, record| {
+                writeln!(
+                    buf,
+                    "{{\"level\":\"{}\",\"target\":\"{}\",\"message\":\"{}\"}}",
+                    record.level(),
+                    escape_json_string(&record.target().to_string()),
+                    escape_json_string(&record.args().to_string())
+                )
+            })
+            .init();
+    }
+
+    /// Escapes `s` so it can be embedded in a JSON string literal: quotes,
+    /// backslashes, and control characters (e.g. a newline in a multi-line
+    /// error message) would otherwise break the one-object-per-line format
+    /// [`init_json_logger`] promises to log collectors.
+    fn escape_json_string(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len());
+
+        for c in s.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    }
 
-    if let Ok(res_ok) = res {
-        Ok(res_ok)
-    } else {
-        Err(globals::DCReadError::ParseError(res.unwrap_err()))
+    /// A [`log::Log`] implementation that forwards records to the local
+    /// syslog daemon, translating [`log::Level`] to the matching syslog
+    /// severity for each record.
+    struct SyslogLogger {
+        writer: std::sync::Mutex<syslog::Logger<syslog::LoggerBackend, syslog::Formatter3164>>,
     }
+
+    impl log::Log for SyslogLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true // filtering is handled by `log::set_max_level`
+        }
+
+        fn log(&self, record: &log::Record) {
+            let Ok(mut writer) = self.writer.lock() else {
+                return;
+            };
+            let message: String = format!("{}", record.args());
+
+            let _ = match level_to_syslog_severity(record.level()) {
+                syslog::Severity::LOG_ERR => writer.err(message),
+                syslog::Severity::LOG_WARNING => writer.warning(message),
+                syslog::Severity::LOG_INFO => writer.info(message),
+                _ => writer.debug(message),
+            };
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Installs a logger that forwards records to the local syslog daemon.
+    fn init_syslog_logger(config: SyslogConfig) {
+        let formatter = syslog::Formatter3164 {
+            facility: config.facility,
+            hostname: None,
+            process: config.process_name,
+            pid: std::process::id() as i32,
+        };
+
+        let writer = match syslog::unix(formatter) {
+            Ok(writer) => writer,
+            Err(err) => {
+                eprintln!("libdonet: failed to connect to syslog, falling back to stderr: {}", err);
+                pretty_env_logger::init();
+                return;
+            }
+        };
+
+        let max_level: log::LevelFilter = std::env::var("RUST_LOG")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(log::LevelFilter::Info);
+
+        let logger = SyslogLogger {
+            writer: std::sync::Mutex::new(writer),
+        };
+
+        log::set_boxed_logger(Box::new(logger))
+            .map(|()| log::set_max_level(max_level))
+            .expect("failed to install syslog logger");
+    }
+
+    /// Converts a [`log::Level`] to its corresponding syslog severity.
+    fn level_to_syslog_severity(level: log::Level) -> syslog::Severity {
+        match level {
+            log::Level::Error => syslog::Severity::LOG_ERR,
+            log::Level::Warn => syslog::Severity::LOG_WARNING,
+            log::Level::Info => syslog::Severity::LOG_INFO,
+            log::Level::Debug => syslog::Severity::LOG_DEBUG,
+            log::Level::Trace => syslog::Severity::LOG_DEBUG,
+        }
+    }
+
+    /// Easy to use interface for the DC file parser. Handles reading
+    /// the DC files, instantiating the lexer and parser, and either
+    /// returns the DCFile object or a Parse/File error.
+    ///
+    /// ## Example Usage
+    /// The following is an example of parsing a simple DC file string,
+    /// printing its DC hash in hexadecimal notation, and accessing
+    /// the elements of a defined Distributed Class:
+    /// ```rust
+    /// use libdonet::dclass::DClass;
+    /// use libdonet::globals::DCReadResult;
+    /// use libdonet::read_dc_files;
+    ///
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let dc_file = "from game.ai import AnonymousContact/UD
+    ///                from game.ai import LoginManager/AI
+    ///                from game.world import DistributedWorld/AI
+    ///                from game.avatar import DistributedAvatar/AI/OV
+    ///
+    ///                dclass AnonymousContact {
+    ///                  login(string username, string password) clsend airecv;
+    ///                };
+    ///
+    ///                dclass LoginManager {
+    ///                  login(channel client, string username, string password) airecv;
+    ///                };
+    ///
+    ///                dclass DistributedWorld {
+    ///                  create_avatar(channel client) airecv;
+    ///                };
+    ///
+    ///                dclass DistributedAvatar {
+    ///                   set_xyzh(int16 x, int16 y, int16 z, int16 h) broadcast required;
+    ///                   indicate_intent(int16 / 10, int16 / 10) ownsend airecv;
+    ///                };";
+    ///
+    /// let dc_read: DCReadResult = read_dc_files(vec![dc_file.into()]);
+    ///
+    /// if let Ok(dc_file) = dc_read {
+    ///     // Print the DC File's 32-bit hash in hexadecimal format.
+    ///     println!("{}", dc_file.borrow_mut().get_pretty_hash());
+    ///     
+    ///     // Retrieve the `DistributedAvatar` dclass by ID.
+    ///     let mut avatar_class = dc_file.borrow_mut().get_dclass_by_id(3);
+    ///
+    ///     // Print the identifier of the dclass.
+    ///     println!("{}", Rc::get_mut(&mut avatar_class).expect("Borrow failed!").get_name());
+    /// }
+    /// ```
+    ///
+    /// The output of the program would be the following:
+    /// ```txt
+    /// 0x01a5fb0c
+    /// DistributedAvatar
+    /// ```
+    /// <br><img src="https://c.tenor.com/myQHgyWQQ9sAAAAd/tenor.gif">
+    ///
+    #[cfg(feature = "dcfile")]
+    pub fn read_dc_files(file_paths: Vec<String>) -> globals::DCReadResult {
+        use crate::parser::lexer::Lexer;
+        use crate::parser::parser::parse;
+        use log::{error, info};
+        use std::cell::RefCell;
+        use std::fs::File;
+        use std::io::Read;
+        use std::rc::Rc;
+
+        init_logger();
+        info!("DC read of {:?}", file_paths);
+
+        let mut file_results: Vec<Result<File, std::io::Error>> = vec![];
+        // All DC files are passed to the lexer as one string.
+        let mut lexer_input: String = String::new();
+
+        assert!(!file_paths.is_empty(), "No DC files given!");
+
+        for file_path in &file_paths {
+            file_results.push(File::open(file_path));
+        }
+
+        for io_result in file_results {
+            if let Ok(mut dcf) = io_result {
+                let res: std::io::Result<usize> = dcf.read_to_string(&mut lexer_input);
+                if let Err(res_err) = res {
+                    // DC file content may not be in proper UTF-8 encoding.
+                    return Err(globals::DCReadError::FileError(res_err));
+                }
+            } else {
+                // Failed to open one of the DC files. (most likely permission error)
+                return Err(globals::DCReadError::FileError(io_result.unwrap_err()));
+            }
+        }
+
+        let lexer: Lexer<'_> = Lexer::new(&lexer_input);
+        let res: Result<Rc<RefCell<dcfile::DCFile>>, globals::ParseError> = parse(lexer);
+
+        if let Ok(res_ok) = res {
+            Ok(res_ok)
+        } else {
+            Err(globals::DCReadError::ParseError(res.unwrap_err()))
+        }
+    }
+
+    } // if #[cfg(feature = "std")]
 }
@@ -0,0 +1,468 @@
+// DONET SOFTWARE
+// Copyright (c) 2023, Donet Authors.
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3.
+// You should have received a copy of this license along
+// with this source code in a file named "LICENSE."
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program; if not, write to the Free Software Foundation,
+// Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+
+// In this module we store all the structures and enums
+// that make up the final generated abstract syntax tree.
+//
+// Every node derives `serde::Serialize`/`Deserialize` behind the `serde`
+// feature flag, so a parsed `DCFile` can be cached to disk or shipped to
+// tooling (e.g. the codegen backend) without re-parsing the source `.dc`
+// file. `Symbol` provides its own impls in `crate::symbol` so that
+// deserializing re-interns identifiers instead of leaking duplicates.
+//
+// `DCToken`/`Span` (from `crate::dclexer`) are part of every node's public
+// fields, so this module's `serde` impls can only compile once that module
+// derives `Serialize`/`Deserialize` for them too; `dclexer` isn't part of
+// this tree yet, so that half of the `--features serde` build is blocked on
+// that module landing, not on anything in this file.
+use crate::dclexer::{DCToken, Span};
+use crate::symbol::Symbol;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Represents a [`Range`] as `{"min": ..., "max": ...}` instead of serde's
+/// default `{"start": ..., "end": ...}`, since `min`/`max` is the field
+/// other services loading this JSON expect. Used via `#[serde(with =
+/// "range_as_min_max")]` on non-optional `Range<T>` fields.
+#[cfg(feature = "serde")]
+mod range_as_min_max {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::ops::Range;
+
+    #[derive(Serialize, Deserialize)]
+    pub(super) struct MinMax<T> {
+        min: T,
+        max: T,
+    }
+
+    impl<T: Clone> From<&Range<T>> for MinMax<T> {
+        fn from(range: &Range<T>) -> Self {
+            MinMax { min: range.start.clone(), max: range.end.clone() }
+        }
+    }
+
+    impl<T> From<MinMax<T>> for Range<T> {
+        fn from(min_max: MinMax<T>) -> Self {
+            min_max.min..min_max.max
+        }
+    }
+
+    pub fn serialize<S, T>(range: &Range<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize + Clone,
+    {
+        MinMax::from(range).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Range<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        Ok(MinMax::<T>::deserialize(deserializer)?.into())
+    }
+}
+
+/// Same as [`range_as_min_max`], but for `Option<Range<T>>` fields: `None`
+/// still serializes to JSON `null`, `Some(range)` to `{"min": ..., "max":
+/// ...}`. Used via `#[serde(with = "option_range_as_min_max")]`.
+#[cfg(feature = "serde")]
+mod option_range_as_min_max {
+    use super::range_as_min_max::MinMax;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::ops::Range;
+
+    pub fn serialize<S, T>(range: &Option<Range<T>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize + Clone,
+    {
+        range.as_ref().map(MinMax::from).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<Range<T>>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        Ok(Option::<MinMax<T>>::deserialize(deserializer)?.map(Into::into))
+    }
+}
+
+pub type IdentifierString = Symbol; // interned identifier
+
+/// A unique, stable identity for an AST node, independent of its `Span`.
+/// Mirrors rustc's `ast::NodeId`: later passes (the resolver, diagnostics,
+/// IDE tooling) key off `NodeId` instead of comparing spans or pointers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Allocates the next `NodeId` from a monotonic, process-wide counter,
+    /// the same way `crate::symbol::intern` hands out `Symbol`s from a
+    /// shared table; the parser calls this once per node it constructs.
+    pub fn fresh() -> Self {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+        NodeId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct DCFile {
+    pub type_decl: Vec<TypeDecl>,
+}
+
+impl DCFile {
+    /// Builds a lookup from every node's [`NodeId`] to its [`Span`], so a
+    /// later pass can report precisely where in the source a problem was
+    /// found without threading a `Span` through its own return type.
+    pub fn node_spans(&self) -> HashMap<NodeId, Span> {
+        let mut spans = HashMap::new();
+        for type_decl in &self.type_decl {
+            collect_type_decl_spans(type_decl, &mut spans);
+        }
+        spans
+    }
+
+    /// Serializes this AST to the stable JSON representation other
+    /// services load (e.g. to avoid re-parsing the source `.dc` file, or to
+    /// hand a parsed file to tooling written outside this crate). This is
+    /// not simply serde's derived output: [`Range`] fields are represented
+    /// as `{"min": ..., "max": ...}` rather than serde's default
+    /// `{"start": ..., "end": ...}` (see `range_as_min_max` above).
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Parses a `DCFile` back from JSON produced by [`DCFile::to_json`].
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+fn collect_type_decl_spans(type_decl: &TypeDecl, spans: &mut HashMap<NodeId, Span>) {
+    spans.insert(type_decl.id, type_decl.span);
+
+    match &type_decl.node {
+        TypeDecl_::KeywordType(k) => {
+            spans.insert(k.id, k.span);
+        }
+        TypeDecl_::StructType(s) => {
+            spans.insert(s.id, s.span);
+            for parameter_field in &s.parameters {
+                collect_parameter_field_spans(parameter_field, spans);
+            }
+        }
+        TypeDecl_::DistributedClassType(dc) => {
+            spans.insert(dc.id, dc.span);
+            for field in &dc.field_declarations {
+                collect_field_spans(field, spans);
+            }
+        }
+        TypeDecl_::DCImport(dci) => {
+            spans.insert(dci.id, dci.span);
+        }
+        TypeDecl_::TypeDefinition(td) => {
+            spans.insert(td.id, td.span);
+            spans.insert(td.dc_type.id, td.dc_type.span);
+        }
+    }
+}
+
+fn collect_field_spans(field: &FieldDecl, spans: &mut HashMap<NodeId, Span>) {
+    spans.insert(field.id, field.span);
+
+    match &field.node {
+        FieldDecl_::MolecularField(mf) => {
+            spans.insert(mf.id, mf.span);
+            match &mf.field_type {
+                FieldType::Atomic(af) => collect_atomic_field_spans(af, spans),
+                FieldType::Parameter(pf) => collect_parameter_field_spans(pf, spans),
+            }
+        }
+        FieldDecl_::AtomicField(af) => collect_atomic_field_spans(af, spans),
+        FieldDecl_::ParameterField(pf) => collect_parameter_field_spans(pf, spans),
+    }
+}
+
+fn collect_atomic_field_spans(atomic_field: &AtomicField, spans: &mut HashMap<NodeId, Span>) {
+    spans.insert(atomic_field.id, atomic_field.span);
+    for parameter in &atomic_field.parameters {
+        collect_parameter_spans(parameter, spans);
+    }
+    if let Some(k) = &atomic_field.keyword_list {
+        spans.insert(k.id, k.span);
+    }
+}
+
+fn collect_parameter_field_spans(parameter_field: &ParameterField, spans: &mut HashMap<NodeId, Span>) {
+    spans.insert(parameter_field.id, parameter_field.span);
+    collect_parameter_spans(&parameter_field.parameter, spans);
+    if let Some(k) = &parameter_field.keyword_list {
+        spans.insert(k.id, k.span);
+    }
+}
+
+fn collect_parameter_spans(parameter: &Parameter, spans: &mut HashMap<NodeId, Span>) {
+    match parameter {
+        Parameter::Char(p) => spans.insert(p.id, p.span),
+        Parameter::Int(p) => spans.insert(p.id, p.span),
+        Parameter::Float(p) => spans.insert(p.id, p.span),
+        Parameter::Sized(p) => spans.insert(p.id, p.span),
+        Parameter::Struct(p) => spans.insert(p.id, p.span),
+        Parameter::Array(p) => {
+            spans.insert(p.data_type.id, p.data_type.span);
+            spans.insert(p.id, p.span)
+        }
+    };
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct TypeDecl {
+    pub id: NodeId,
+    pub span: Span,
+    pub node: TypeDecl_,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub enum TypeDecl_ {
+    KeywordType(KeywordType),
+    StructType(StructType),
+    DistributedClassType(DistributedClassType),
+    DCImport(DCImport),
+    TypeDefinition(TypeDefinition),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct KeywordType {
+    pub id: NodeId,
+    pub span: Span,
+    pub node: KeywordType_,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub enum KeywordType_ {
+    KeywordType(IdentifierString),
+    KeywordList(Vec<IdentifierString>),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct StructType {
+    pub id: NodeId,
+    pub span: Span,
+    pub identifier: IdentifierString,
+    pub parameters: Vec<ParameterField>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct DistributedClassType {
+    pub id: NodeId,
+    pub span: Span,
+    pub identifier: IdentifierString,
+    pub field_declarations: Vec<FieldDecl>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct DCImport {
+    pub id: NodeId,
+    pub span: Span,
+    pub module: Vec<IdentifierString>, // python filename, or module(s)
+    pub module_views: Vec<IdentifierString>,
+    pub class: IdentifierString,
+    pub class_views: Vec<IdentifierString>, // AI, UD, OV ...
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct TypeDefinition {
+    pub id: NodeId,
+    pub span: Span,
+    pub dc_type: DataType,
+    pub alias: IdentifierString,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct FieldDecl {
+    pub id: NodeId,
+    pub span: Span,
+    pub node: FieldDecl_,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub enum FieldDecl_ {
+    MolecularField(MolecularField),
+    AtomicField(AtomicField),
+    ParameterField(ParameterField),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct MolecularField {
+    pub id: NodeId,
+    pub span: Span,
+    pub identifier: IdentifierString,
+    pub field_type: FieldType,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub enum FieldType {
+    Atomic(AtomicField),
+    Parameter(ParameterField),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct AtomicField {
+    pub id: NodeId,
+    pub span: Span,
+    pub identifier: IdentifierString,
+    pub parameters: Vec<Parameter>,
+    pub keyword_list: Option<KeywordType>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct ParameterField {
+    pub id: NodeId,
+    pub span: Span,
+    pub parameter: Parameter,
+    pub keyword_list: Option<KeywordType>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub enum Parameter {
+    Char(CharParameter),
+    Int(IntParameter),
+    Float(FloatParameter),
+    Sized(SizedParameter),
+    Struct(StructParameter),
+    Array(ArrayParameter),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct CharParameter {
+    pub id: NodeId,
+    pub span: Span,
+    pub char_type: Option<IdentifierString>,
+    pub char_literal: Option<char>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct IntParameter {
+    pub id: NodeId,
+    pub span: Span,
+    pub identifier: Option<IdentifierString>,
+    pub int_type: Option<IdentifierString>,
+    #[cfg_attr(feature = "serde", serde(with = "option_range_as_min_max"))]
+    pub int_range: Option<Range<i64>>,
+    pub int_transform: Option<IntTransform>,
+    pub int_constant: Option<i64>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct FloatParameter {
+    pub id: NodeId,
+    pub span: Span,
+    pub identifier: Option<IdentifierString>,
+    pub float_type: Option<IdentifierString>,
+    #[cfg_attr(feature = "serde", serde(with = "option_range_as_min_max"))]
+    pub float_range: Option<Range<f64>>,
+    pub float_transform: Option<FloatTransform>,
+    pub float_constant: Option<f64>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct SizedParameter {
+    pub id: NodeId,
+    pub span: Span,
+    pub sized_type: Option<IdentifierString>,
+    pub size_constraint: Option<i64>,
+    pub identifier: Option<IdentifierString>,
+    pub string_literal: Option<String>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct StructParameter {
+    pub id: NodeId,
+    pub span: Span,
+    pub identifier1: IdentifierString,
+    pub identifier2: Option<IdentifierString>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct ArrayParameter {
+    pub id: NodeId,
+    pub span: Span,
+    pub data_type: DataType,
+    pub identifier: Option<IdentifierString>,
+    #[cfg_attr(feature = "serde", serde(with = "range_as_min_max"))]
+    pub array_range: Range<i64>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub struct DataType {
+    pub id: NodeId,
+    pub span: Span,
+    pub base_type: BaseType,
+    pub identifier: Option<String>, // used for IntType (unsigned/signed + bits)
+}
+
+#[rustfmt::skip]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseType {
+    CharType, IntType, FloatType,
+    StringType, BlobType, StructType,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub enum IntTransform {
+    OperatorIntLiteral { operator: DCToken, int_literal: i32 },
+    ParenthesizedIntTransform(Box<IntTransform>),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq)]
+pub enum FloatTransform {
+    OperatorFloatLiteral { operator: DCToken, float_literal: f32 },
+    ParenthesizedFloatTransform(Box<FloatTransform>),
+}
@@ -0,0 +1,277 @@
+// DONET SOFTWARE
+// Copyright (c) 2023, Donet Authors.
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3.
+// You should have received a copy of this license along
+// with this source code in a file named "LICENSE."
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program; if not, write to the Free Software Foundation,
+// Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+
+// Emits client/AI language bindings from a parsed `DCFile`, so game code
+// can call distributed methods without hand-writing marshalling. Modeled
+// after cbindgen: a `CodegenConfig` selects the target language/style, a
+// `Writer` owns indentation and the include/import preamble, and an
+// `Emitter` walks the AST via `DCVisitor`, formatting each node according
+// to its `BaseType` (`IntType` bit-width -> `int32_t`/`uint16_t`, `BlobType`
+// -> byte buffer, ...).
+use crate::ast::*;
+use crate::visit::{self, DCVisitor};
+
+/// Target language for emitted bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Python,
+    Cpp,
+}
+
+/// Selects the target language and output style for a codegen run.
+#[derive(Debug, Clone)]
+pub struct CodegenConfig {
+    pub language: Language,
+    /// Indentation string used for each nesting level.
+    pub indent: String,
+}
+
+impl CodegenConfig {
+    pub fn new(language: Language) -> Self {
+        Self { language, indent: "    ".to_string() }
+    }
+}
+
+/// Owns the output buffer and indentation depth while walking the AST.
+struct Writer {
+    buf: String,
+    indent: String,
+    depth: usize,
+}
+
+impl Writer {
+    fn new(indent: String) -> Self {
+        Self { buf: String::new(), indent, depth: 0 }
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.depth {
+            self.buf.push_str(&self.indent);
+        }
+        self.buf.push_str(text);
+        self.buf.push('\n');
+    }
+
+    fn blank(&mut self) {
+        self.buf.push('\n');
+    }
+}
+
+/// Walks a parsed [`DCFile`] and emits one class stub per
+/// [`DistributedClassType`], with methods generated from [`AtomicField`]
+/// signatures and accessors from [`ParameterField`]s.
+pub struct Emitter {
+    config: CodegenConfig,
+    out: Writer,
+}
+
+impl Emitter {
+    pub fn new(config: CodegenConfig) -> Self {
+        let indent = config.indent.clone();
+        Self { config, out: Writer::new(indent) }
+    }
+
+    /// Renders `file` and returns the generated source text.
+    pub fn emit(mut self, file: &DCFile) -> String {
+        self.preamble();
+        self.visit_file(file);
+        self.out.buf
+    }
+
+    fn preamble(&mut self) {
+        match self.config.language {
+            Language::Python => {
+                self.out.line("# Auto-generated by donet's codegen backend. Do not edit by hand.");
+                self.out.blank();
+            }
+            Language::Cpp => {
+                self.out.line("// Auto-generated by donet's codegen backend. Do not edit by hand.");
+                self.out.line("#pragma once");
+                self.out.blank();
+                self.out.line("#include <cstdint>");
+                self.out.line("#include <string>");
+                self.out.line("#include <vector>");
+                self.out.blank();
+            }
+        }
+    }
+
+    /// Maps a base type and its optional width/signedness identifier (e.g.
+    /// `IntType` with `"uint16"`) to the target language's spelling of it.
+    fn base_type_name(&self, base_type: &BaseType, identifier: Option<&str>) -> String {
+        match self.config.language {
+            Language::Python => match base_type {
+                BaseType::CharType => "str".to_string(),
+                BaseType::IntType => "int".to_string(),
+                BaseType::FloatType => "float".to_string(),
+                BaseType::StringType => "str".to_string(),
+                BaseType::BlobType => "bytes".to_string(),
+                BaseType::StructType => identifier.unwrap_or("object").to_string(),
+            },
+            Language::Cpp => match base_type {
+                BaseType::CharType => "char".to_string(),
+                BaseType::IntType => int_type_to_cpp(identifier),
+                BaseType::FloatType => "double".to_string(),
+                BaseType::StringType => "std::string".to_string(),
+                BaseType::BlobType => "std::vector<uint8_t>".to_string(),
+                BaseType::StructType => identifier.unwrap_or("void*").to_string(),
+            },
+        }
+    }
+
+    /// Maps a [`DataType`] node to the target language's spelling of it.
+    fn type_name(&self, data_type: &DataType) -> String {
+        self.base_type_name(&data_type.base_type, data_type.identifier.as_deref())
+    }
+
+    /// Maps a field [`Parameter`] to its `(name, type)` pair for use in a
+    /// method signature or accessor.
+    fn parameter_signature(&self, parameter: &Parameter) -> (String, String) {
+        match parameter {
+            // `CharParameter` carries no name, so it always gets the default.
+            Parameter::Char(_) => ("value".to_string(), self.base_type_name(&BaseType::CharType, None)),
+            Parameter::Int(p) => (
+                ident_or("value", p.identifier.as_ref()),
+                self.base_type_name(&BaseType::IntType, p.int_type.as_ref().map(|s| s.as_str())),
+            ),
+            Parameter::Float(p) => (
+                ident_or("value", p.identifier.as_ref()),
+                self.base_type_name(&BaseType::FloatType, p.float_type.as_ref().map(|s| s.as_str())),
+            ),
+            Parameter::Sized(p) => (ident_or("value", p.identifier.as_ref()), self.base_type_name(&BaseType::BlobType, None)),
+            Parameter::Struct(p) => (ident_or("value", p.identifier2.as_ref()), p.identifier1.to_string()),
+            Parameter::Array(p) => {
+                let element_type = self.type_name(&p.data_type);
+                let array_type = match self.config.language {
+                    Language::Python => format!("list[{}]", element_type),
+                    Language::Cpp => format!("std::vector<{}>", element_type),
+                };
+                (ident_or("value", p.identifier.as_ref()), array_type)
+            }
+        }
+    }
+
+    fn emit_method_signature(&self, name: &str, parameters: &[Parameter]) -> String {
+        let args: Vec<String> = parameters
+            .iter()
+            .map(|p| {
+                let (arg_name, arg_type) = self.parameter_signature(p);
+                match self.config.language {
+                    Language::Python => format!("{}: {}", arg_name, arg_type),
+                    Language::Cpp => format!("{} {}", arg_type, arg_name),
+                }
+            })
+            .collect();
+
+        match self.config.language {
+            Language::Python => format!("def {}(self, {}) -> None:", name, args.join(", ")),
+            Language::Cpp => format!("void {}({});", name, args.join(", ")),
+        }
+    }
+}
+
+impl DCVisitor for Emitter {
+    fn visit_distributed_class(&mut self, dclass: &DistributedClassType) {
+        match self.config.language {
+            Language::Python => self.out.line(&format!("class {}:", dclass.identifier)),
+            Language::Cpp => self.out.line(&format!("class {} {{", dclass.identifier)),
+        }
+        self.out.depth += 1;
+        if self.config.language == Language::Cpp {
+            // Every member below is a generated method/accessor meant to be
+            // called from client code, so it has to be public; C++ classes
+            // (unlike structs) default new members to private.
+            self.out.line("public:");
+        }
+
+        visit::walk_distributed_class(self, dclass);
+
+        self.out.depth -= 1;
+        if self.config.language == Language::Cpp {
+            self.out.line("};");
+        }
+        self.out.blank();
+    }
+
+    fn visit_atomic_field(&mut self, atomic_field: &AtomicField) {
+        let signature = self.emit_method_signature(&atomic_field.identifier.to_string(), &atomic_field.parameters);
+
+        match self.config.language {
+            Language::Python => {
+                self.out.line(&signature);
+                self.out.depth += 1;
+                self.out.line("...");
+                self.out.depth -= 1;
+            }
+            Language::Cpp => self.out.line(&signature),
+        }
+    }
+
+    fn visit_parameter_field(&mut self, parameter_field: &ParameterField) {
+        if let Parameter::Int(IntParameter { identifier: Some(name), int_constant: Some(value), int_type, .. }) =
+            &parameter_field.parameter
+        {
+            // A constant-valued field is emitted as a constant, not an accessor.
+            let type_name = self.base_type_name(&BaseType::IntType, int_type.as_ref().map(|s| s.as_str()));
+            match self.config.language {
+                Language::Python => self.out.line(&format!("{}: {} = {}", name, type_name, value)),
+                Language::Cpp => self.out.line(&format!("static constexpr {} {} = {};", type_name, name, value)),
+            }
+            return;
+        }
+
+        let (field_name, field_type) = self.parameter_signature(&parameter_field.parameter);
+
+        match self.config.language {
+            Language::Python => {
+                self.out.line(&format!("def get_{}(self) -> {}:", field_name, field_type));
+                self.out.depth += 1;
+                self.out.line("...");
+                self.out.depth -= 1;
+                self.out.line(&format!("def set_{}(self, value: {}) -> None:", field_name, field_type));
+                self.out.depth += 1;
+                self.out.line("...");
+                self.out.depth -= 1;
+            }
+            Language::Cpp => {
+                self.out.line(&format!("{} get_{}() const;", field_type, field_name));
+                self.out.line(&format!("void set_{}({} value);", field_name, field_type));
+            }
+        }
+    }
+}
+
+fn ident_or(default_name: &str, identifier: Option<&IdentifierString>) -> String {
+    identifier.map(|s| s.to_string()).unwrap_or_else(|| default_name.to_string())
+}
+
+/// Maps a DC `int_type` identifier (e.g. `"uint16"`) to its C++ spelling.
+/// Falls back to plain `int32_t` when no width/signedness was given.
+fn int_type_to_cpp(int_type: Option<&str>) -> String {
+    match int_type {
+        Some("int8") => "int8_t".to_string(),
+        Some("int16") => "int16_t".to_string(),
+        Some("int32") => "int32_t".to_string(),
+        Some("int64") => "int64_t".to_string(),
+        Some("uint8") => "uint8_t".to_string(),
+        Some("uint16") => "uint16_t".to_string(),
+        Some("uint32") => "uint32_t".to_string(),
+        Some("uint64") => "uint64_t".to_string(),
+        Some(other) => other.to_string(),
+        None => "int32_t".to_string(),
+    }
+}
@@ -75,9 +75,11 @@ pub mod datagram {
     // All possible errors that can be returned within Datagram's implementation.
     pub enum DgError {
         DatagramOverflow,
+        DatagramOverread,
     }
 
     pub type DgResult = Result<(), DgError>;
+    pub type DgBufferResult<T> = Result<T, DgError>;
 
     pub struct Datagram {
         buffer: Vec<u8>,
@@ -306,7 +308,241 @@ pub mod datagram {
         }
     }
 
-    //pub struct DatagramIterator {
+    // Iterates over a Datagram's buffer, keeping a read offset so that
+    // typed values can be pulled back out in the same order they were
+    // written in. Mirrors Datagram's add_* API in reverse.
+    pub struct DatagramIterator {
+        buffer: Vec<u8>,
+        offset: usize,
+    }
+
+    impl DatagramIterator {
+        pub fn new(dg: Datagram) -> DatagramIterator {
+            DatagramIterator {
+                buffer: dg.buffer,
+                offset: 0,
+            }
+        }
+
+        // Checks if we can read `length` number of bytes from the buffer.
+        fn check_read_length(&self, length: DgSize) -> DgResult {
+            let new_offset: usize = self.offset + usize::from(length);
+
+            if new_offset > self.buffer.len() {
+                // TODO: log error with more information
+                return Err(DgError::DatagramOverread);
+            }
+            return Ok(());
+        }
+
+        // Returns the number of unread bytes left in the datagram.
+        pub fn remaining(&self) -> DgSize {
+            (self.buffer.len() - self.offset) as DgSize
+        }
+
+        // Returns the current read offset, in bytes, from the start of the buffer.
+        pub fn tell(&self) -> DgSize {
+            self.offset as DgSize
+        }
+
+        // Manually sets the read offset, in bytes, from the start of the buffer.
+        pub fn seek(&mut self, offset: DgSize) {
+            self.offset = usize::from(offset);
+        }
+
+        // Reads `size` number of bytes without advancing the read offset.
+        pub fn peek(&self, size: DgSize) -> DgBufferResult<Vec<u8>> {
+            let res: DgResult = self.check_read_length(size);
+            if res.is_err() {
+                return Err(res.unwrap_err());
+            }
+            Ok(self.buffer[self.offset..self.offset + usize::from(size)].to_vec())
+        }
+
+        pub fn read_bool(&mut self) -> DgBufferResult<bool> {
+            let res: DgBufferResult<u8> = self.read_u8();
+            match res {
+                Ok(v) => Ok(v != 0),
+                Err(err) => Err(err),
+            }
+        }
+
+        pub fn read_u8(&mut self) -> DgBufferResult<u8> {
+            let res: DgResult = self.check_read_length(1);
+            if res.is_err() {
+                return Err(res.unwrap_err());
+            }
+            let v: u8 = self.buffer[self.offset];
+            self.offset += 1;
+            return Ok(v);
+        }
+
+        // The datagram wire format is little-endian, so the bytes are
+        // assembled directly via `from_le_bytes`; this already produces the
+        // correct value on every host, so there's no separate swap step
+        // (unlike `Datagram::add_u16`/`add_u32`/`add_u64`, which build the
+        // buffer byte-by-byte and so do need `endianness::swap_le_*`).
+        pub fn read_u16(&mut self) -> DgBufferResult<u16> {
+            let res: DgResult = self.check_read_length(2);
+            if res.is_err() {
+                return Err(res.unwrap_err());
+            }
+            let v: u16 = u16::from_le_bytes([self.buffer[self.offset], self.buffer[self.offset + 1]]);
+            self.offset += 2;
+            return Ok(v);
+        }
+
+        pub fn read_u32(&mut self) -> DgBufferResult<u32> {
+            let res: DgResult = self.check_read_length(4);
+            if res.is_err() {
+                return Err(res.unwrap_err());
+            }
+            let v: u32 = u32::from_le_bytes([
+                self.buffer[self.offset],
+                self.buffer[self.offset + 1],
+                self.buffer[self.offset + 2],
+                self.buffer[self.offset + 3],
+            ]);
+            self.offset += 4;
+            return Ok(v);
+        }
 
-    //}
+        pub fn read_u64(&mut self) -> DgBufferResult<u64> {
+            let res: DgResult = self.check_read_length(8);
+            if res.is_err() {
+                return Err(res.unwrap_err());
+            }
+            let v: u64 = u64::from_le_bytes([
+                self.buffer[self.offset],
+                self.buffer[self.offset + 1],
+                self.buffer[self.offset + 2],
+                self.buffer[self.offset + 3],
+                self.buffer[self.offset + 4],
+                self.buffer[self.offset + 5],
+                self.buffer[self.offset + 6],
+                self.buffer[self.offset + 7],
+            ]);
+            self.offset += 8;
+            return Ok(v);
+        }
+
+        // signed integer aliases. same bitwise operations.
+        pub fn read_i8(&mut self) -> DgBufferResult<i8> {
+            return self.read_u8().map(|v| v as i8);
+        }
+
+        pub fn read_i16(&mut self) -> DgBufferResult<i16> {
+            return self.read_u16().map(|v| v as i16);
+        }
+
+        pub fn read_i32(&mut self) -> DgBufferResult<i32> {
+            return self.read_u32().map(|v| v as i32);
+        }
+
+        pub fn read_i64(&mut self) -> DgBufferResult<i64> {
+            return self.read_u64().map(|v| v as i64);
+        }
+
+        // 32-bit IEEE 754 floating point. same bitwise operations.
+        pub fn read_f32(&mut self) -> DgBufferResult<f32> {
+            return self.read_u32().map(|v| v as f32);
+        }
+
+        // 64-bit IEEE 754 floating point. same bitwise operations.
+        pub fn read_f64(&mut self) -> DgBufferResult<f64> {
+            return self.read_u64().map(|v| v as f64);
+        }
+
+        // Reads a 64-bit channel ID from the datagram.
+        pub fn read_channel(&mut self) -> DgBufferResult<types::Channel> {
+            return self.read_u64().map(|v| v as types::Channel);
+        }
+
+        // Reads a 32-bit Distributed Object ID from the datagram.
+        pub fn read_doid(&mut self) -> DgBufferResult<types::DoId> {
+            return self.read_u32().map(|v| v as types::DoId);
+        }
+
+        // Reads a 32-bit zone ID from the datagram.
+        pub fn read_zone(&mut self) -> DgBufferResult<types::Zone> {
+            return self.read_u32().map(|v| v as types::Zone);
+        }
+
+        // Reads a parent/zone location pair, added via add_location().
+        pub fn read_location(&mut self) -> DgBufferResult<(types::DoId, types::Zone)> {
+            let parent: types::DoId = self.read_doid()?;
+            let zone: types::Zone = self.read_zone()?;
+            return Ok((parent, zone));
+        }
+
+        // Reads `size` number of raw bytes from the datagram.
+        pub fn read_data(&mut self, size: DgSize) -> DgBufferResult<Vec<u8>> {
+            let res: DgResult = self.check_read_length(size);
+            if res.is_err() {
+                return Err(res.unwrap_err());
+            }
+            let data: Vec<u8> = self.buffer[self.offset..self.offset + usize::from(size)].to_vec();
+            self.offset += usize::from(size);
+            return Ok(data);
+        }
+
+        // Reads a dclass string value, prefixed with a 16-bit length tag.
+        pub fn read_string(&mut self) -> DgBufferResult<String> {
+            let length: u16 = self.read_u16()?;
+            let data: Vec<u8> = self.read_data(length)?;
+
+            match String::from_utf8(data) {
+                Ok(s) => Ok(s),
+                Err(_) => Err(DgError::DatagramOverread),
+            }
+        }
+
+        // Reads a dclass blob value, prefixed with a 16-bit length tag.
+        pub fn read_blob(&mut self) -> DgBufferResult<Vec<u8>> {
+            let length: u16 = self.read_u16()?;
+            return self.read_data(length);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{Datagram, DatagramIterator};
+
+        fn dg_of(bytes: &[u8]) -> Datagram {
+            let mut dg = Datagram::new();
+            for &b in bytes {
+                dg.add_u8(b).unwrap_or_else(|_| panic!("add_u8 failed"));
+            }
+            dg
+        }
+
+        // A byte buffer must decode to the same value on every host, no
+        // matter the host's own endianness.
+        #[test]
+        fn read_u16_is_little_endian_regardless_of_host() {
+            let mut dgi = DatagramIterator::new(dg_of(&[0x01, 0x02]));
+            match dgi.read_u16() {
+                Ok(v) => assert_eq!(v, 0x0201),
+                Err(_) => panic!("read_u16 failed"),
+            }
+        }
+
+        #[test]
+        fn read_u32_is_little_endian_regardless_of_host() {
+            let mut dgi = DatagramIterator::new(dg_of(&[0x01, 0x02, 0x03, 0x04]));
+            match dgi.read_u32() {
+                Ok(v) => assert_eq!(v, 0x04030201),
+                Err(_) => panic!("read_u32 failed"),
+            }
+        }
+
+        #[test]
+        fn read_u64_is_little_endian_regardless_of_host() {
+            let mut dgi = DatagramIterator::new(dg_of(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]));
+            match dgi.read_u64() {
+                Ok(v) => assert_eq!(v, 0x0807060504030201),
+                Err(_) => panic!("read_u64 failed"),
+            }
+        }
+    }
 }
@@ -0,0 +1,329 @@
+// DONET SOFTWARE
+// Copyright (c) 2023, Donet Authors.
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3.
+// You should have received a copy of this license along
+// with this source code in a file named "LICENSE."
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program; if not, write to the Free Software Foundation,
+// Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+
+// The inverse of `dcparser`: turns a parsed `DCFile` back into canonical
+// `.dc` source text. Field ordering always matches declaration order and
+// spacing is fixed, so two ASTs that are equal produce byte-identical
+// output.
+//
+// Scope note: this module does not (yet) guarantee `parse(lex(emit(f)))
+// == f`. `dcparser`'s `distributed_class_type` and `parameter` grammar
+// rules are still stubs (the former doesn't capture a dclass' inheritance
+// list or fields at all), and there's no `dclexer` in this tree to
+// tokenize the emitted text in the first place, so there is currently no
+// working parser to round-trip through. What this module does guarantee:
+// emitting a given `DCFile` is deterministic, and every token it writes
+// (including transform operators, previously emitted via `{:?}`) is valid
+// `.dc` syntax rather than a `Debug` dump. The round trip is a goal for
+// once `dcparser`/`dclexer` are complete, not a property of the code here
+// today; its tests check the sub-property above, not a full fixpoint.
+use std::io::{self, Write};
+
+use crate::ast::*;
+use crate::dclexer::DCToken;
+
+impl DCFile {
+    /// Writes `self` back out as canonical `.dc` source text.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for (i, type_decl) in self.type_decl.iter().enumerate() {
+            if i > 0 {
+                writeln!(w)?;
+            }
+            write_type_decl(w, type_decl)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_type_decl(w: &mut impl Write, type_decl: &TypeDecl) -> io::Result<()> {
+    match &type_decl.node {
+        TypeDecl_::KeywordType(k) => write_keyword_type(w, k),
+        TypeDecl_::StructType(s) => write_struct(w, s),
+        TypeDecl_::DistributedClassType(dc) => write_dclass(w, dc),
+        TypeDecl_::DCImport(dci) => write_dc_import(w, dci),
+        TypeDecl_::TypeDefinition(td) => write_type_definition(w, td),
+    }
+}
+
+fn write_keyword_type(w: &mut impl Write, keyword_type: &KeywordType) -> io::Result<()> {
+    match &keyword_type.node {
+        KeywordType_::KeywordType(k) => writeln!(w, "keyword {};", k),
+        KeywordType_::KeywordList(ks) => {
+            let joined: Vec<String> = ks.iter().map(|k| k.to_string()).collect();
+            writeln!(w, "keyword {};", joined.join(" "))
+        }
+    }
+}
+
+fn write_struct(w: &mut impl Write, struct_type: &StructType) -> io::Result<()> {
+    writeln!(w, "struct {} {{", struct_type.identifier)?;
+    for parameter_field in &struct_type.parameters {
+        write!(w, "  ")?;
+        write_parameter_field(w, parameter_field)?;
+        writeln!(w, ";")?;
+    }
+    writeln!(w, "}};")
+}
+
+// NOTE: `DistributedClassType` doesn't carry an inheritance list yet (the
+// grammar's `distributed_class_type` rule is still a stub), so this only
+// reconstructs the `dclass Name { ... };` shell and its fields. Once
+// parent classes are parsed, emit them here as `dclass Name : Parent1,
+// Parent2 {`.
+fn write_dclass(w: &mut impl Write, dclass: &DistributedClassType) -> io::Result<()> {
+    writeln!(w, "dclass {} {{", dclass.identifier)?;
+    for field in &dclass.field_declarations {
+        write!(w, "  ")?;
+        write_field(w, field)?;
+        writeln!(w, ";")?;
+    }
+    writeln!(w, "}};")
+}
+
+fn write_dc_import(w: &mut impl Write, dc_import: &DCImport) -> io::Result<()> {
+    let module: Vec<String> = dc_import.module.iter().map(|m| m.to_string()).collect();
+    write!(w, "from {}", module.join("."))?;
+    for view in &dc_import.module_views {
+        write!(w, "/{}", view)?;
+    }
+    write!(w, " import {}", dc_import.class)?;
+    for view in &dc_import.class_views {
+        write!(w, "/{}", view)?;
+    }
+    writeln!(w)
+}
+
+fn write_type_definition(w: &mut impl Write, type_definition: &TypeDefinition) -> io::Result<()> {
+    writeln!(w, "typedef {} {};", write_data_type(&type_definition.dc_type), type_definition.alias)
+}
+
+fn write_data_type(data_type: &DataType) -> String {
+    match data_type.base_type {
+        BaseType::CharType => "char".to_string(),
+        BaseType::IntType => data_type.identifier.clone().unwrap_or_else(|| "int32".to_string()),
+        BaseType::FloatType => "float64".to_string(),
+        BaseType::StringType => "string".to_string(),
+        BaseType::BlobType => "blob".to_string(),
+        BaseType::StructType => data_type.identifier.clone().unwrap_or_default(),
+    }
+}
+
+fn write_field(w: &mut impl Write, field: &FieldDecl) -> io::Result<()> {
+    match &field.node {
+        FieldDecl_::MolecularField(mf) => write_molecular_field(w, mf),
+        FieldDecl_::AtomicField(af) => write_atomic_field(w, af),
+        FieldDecl_::ParameterField(pf) => write_parameter_field(w, pf),
+    }
+}
+
+fn write_molecular_field(w: &mut impl Write, molecular_field: &MolecularField) -> io::Result<()> {
+    write!(w, "{} : ", molecular_field.identifier)?;
+    match &molecular_field.field_type {
+        FieldType::Atomic(af) => write_atomic_field(w, af),
+        FieldType::Parameter(pf) => write_parameter_field(w, pf),
+    }
+}
+
+fn write_atomic_field(w: &mut impl Write, atomic_field: &AtomicField) -> io::Result<()> {
+    write!(w, "{}(", atomic_field.identifier)?;
+    for (i, parameter) in atomic_field.parameters.iter().enumerate() {
+        if i > 0 {
+            write!(w, ", ")?;
+        }
+        write!(w, "{}", write_parameter(parameter))?;
+    }
+    write!(w, ")")?;
+    write_keyword_list(w, &atomic_field.keyword_list)
+}
+
+fn write_parameter_field(w: &mut impl Write, parameter_field: &ParameterField) -> io::Result<()> {
+    write!(w, "{}", write_parameter(&parameter_field.parameter))?;
+    write_keyword_list(w, &parameter_field.keyword_list)
+}
+
+fn write_keyword_list(w: &mut impl Write, keyword_list: &Option<KeywordType>) -> io::Result<()> {
+    let Some(keywords) = keyword_list else {
+        return Ok(());
+    };
+
+    match &keywords.node {
+        KeywordType_::KeywordType(k) => write!(w, " {}", k),
+        KeywordType_::KeywordList(ks) => {
+            for k in ks {
+                write!(w, " {}", k)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_parameter(parameter: &Parameter) -> String {
+    match parameter {
+        Parameter::Char(p) => write_char_parameter(p),
+        Parameter::Int(p) => write_int_parameter(p),
+        Parameter::Float(p) => write_float_parameter(p),
+        Parameter::Sized(p) => write_sized_parameter(p),
+        Parameter::Struct(p) => write_struct_parameter(p),
+        Parameter::Array(p) => write_array_parameter(p),
+    }
+}
+
+fn write_char_parameter(p: &CharParameter) -> String {
+    let mut s = p.char_type.map(|t| t.to_string()).unwrap_or_else(|| "char".to_string());
+    if let Some(literal) = p.char_literal {
+        s.push_str(&format!(" = '{}'", literal));
+    }
+    s
+}
+
+fn write_int_parameter(p: &IntParameter) -> String {
+    let mut s = p.int_type.map(|t| t.to_string()).unwrap_or_else(|| "int32".to_string());
+    if let Some(range) = &p.int_range {
+        s.push_str(&format!("({}-{})", range.start, range.end));
+    }
+    if let Some(transform) = &p.int_transform {
+        s.push_str(&format!(" {}", write_int_transform(transform)));
+    }
+    if let Some(identifier) = &p.identifier {
+        s.push_str(&format!(" {}", identifier));
+    }
+    if let Some(constant) = p.int_constant {
+        s.push_str(&format!(" = {}", constant));
+    }
+    s
+}
+
+fn write_float_parameter(p: &FloatParameter) -> String {
+    let mut s = p.float_type.map(|t| t.to_string()).unwrap_or_else(|| "float64".to_string());
+    if let Some(range) = &p.float_range {
+        s.push_str(&format!("({}-{})", range.start, range.end));
+    }
+    if let Some(transform) = &p.float_transform {
+        s.push_str(&format!(" {}", write_float_transform(transform)));
+    }
+    if let Some(identifier) = &p.identifier {
+        s.push_str(&format!(" {}", identifier));
+    }
+    if let Some(constant) = p.float_constant {
+        s.push_str(&format!(" = {}", constant));
+    }
+    s
+}
+
+fn write_sized_parameter(p: &SizedParameter) -> String {
+    let mut s = p.sized_type.map(|t| t.to_string()).unwrap_or_else(|| "string".to_string());
+    if let Some(size) = p.size_constraint {
+        s.push_str(&format!("({})", size));
+    }
+    if let Some(identifier) = &p.identifier {
+        s.push_str(&format!(" {}", identifier));
+    }
+    if let Some(literal) = &p.string_literal {
+        s.push_str(&format!(" = \"{}\"", literal));
+    }
+    s
+}
+
+fn write_struct_parameter(p: &StructParameter) -> String {
+    let mut s = p.identifier1.to_string();
+    if let Some(identifier2) = &p.identifier2 {
+        s.push_str(&format!(" {}", identifier2));
+    }
+    s
+}
+
+fn write_array_parameter(p: &ArrayParameter) -> String {
+    let mut s = write_data_type(&p.data_type);
+    s.push_str(&format!("[{}-{}]", p.array_range.start, p.array_range.end));
+    if let Some(identifier) = &p.identifier {
+        s.push_str(&format!(" {}", identifier));
+    }
+    s
+}
+
+// The numeric-transform operator tokens the grammar accepts (mirrors
+// `libdonet`'s `int_transform` rule): `%`, `/`, `*`, `-`, `+`. Emitting `operator`'s `Debug` form (e.g. `Percent`) isn't
+// valid `.dc` and can't re-lex, so spell out the real token instead.
+fn write_operator(operator: &DCToken) -> &'static str {
+    match operator {
+        DCToken::Percent => "%",
+        DCToken::ForwardSlash => "/",
+        DCToken::Star => "*",
+        DCToken::Hyphen => "-",
+        DCToken::Plus => "+",
+        other => panic!("unexpected numeric transform operator token: {other:?}"),
+    }
+}
+
+fn write_int_transform(transform: &IntTransform) -> String {
+    match transform {
+        IntTransform::OperatorIntLiteral { operator, int_literal } => {
+            format!("{} {}", write_operator(operator), int_literal)
+        }
+        IntTransform::ParenthesizedIntTransform(inner) => format!("({})", write_int_transform(inner)),
+    }
+}
+
+fn write_float_transform(transform: &FloatTransform) -> String {
+    match transform {
+        FloatTransform::OperatorFloatLiteral { operator, float_literal } => {
+            format!("{} {}", write_operator(operator), float_literal)
+        }
+        FloatTransform::ParenthesizedFloatTransform(inner) => format!("({})", write_float_transform(inner)),
+    }
+}
+
+// These tests cover the emitter in isolation, not a `parse(lex(emit(f)))
+// == f` round trip (see the module-level scope note above for why that
+// isn't possible here yet). They pin down the one thing that made the
+// round trip impossible in principle on top of the missing parser/lexer:
+// the transform operator's emitted spelling has to be a real `.dc` token,
+// not a `Debug` dump of the AST.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_transform_emits_real_operator_tokens_not_debug_form() {
+        let cases = [
+            (DCToken::Percent, "%"),
+            (DCToken::ForwardSlash, "/"),
+            (DCToken::Star, "*"),
+            (DCToken::Hyphen, "-"),
+            (DCToken::Plus, "+"),
+        ];
+        for (operator, token) in cases {
+            let transform = IntTransform::OperatorIntLiteral { operator, int_literal: 10 };
+            assert_eq!(write_int_transform(&transform), format!("{token} 10"));
+        }
+    }
+
+    #[test]
+    fn float_transform_emits_real_operator_tokens_not_debug_form() {
+        let transform = FloatTransform::OperatorFloatLiteral { operator: DCToken::Star, float_literal: 2.5 };
+        assert_eq!(write_float_transform(&transform), "* 2.5");
+    }
+
+    #[test]
+    fn parenthesized_int_transform_nests_correctly() {
+        let transform = IntTransform::ParenthesizedIntTransform(Box::new(IntTransform::OperatorIntLiteral {
+            operator: DCToken::Plus,
+            int_literal: 5,
+        }));
+        assert_eq!(write_int_transform(&transform), "(+ 5)");
+    }
+}
@@ -0,0 +1,281 @@
+// DONET SOFTWARE
+// Copyright (c) 2023, Donet Authors.
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3.
+// You should have received a copy of this license along
+// with this source code in a file named "LICENSE."
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program; if not, write to the Free Software Foundation,
+// Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+
+// Computes the legacy 32-bit DC file hash, matching the algorithm used by
+// Panda3D's DClass library (and `donet-core`'s `hashgen` module): every
+// consecutive integer fed into the generator is multiplied by the next
+// prime number and added to a running total, truncated to 32 bits. Clients
+// and the server compare this hash on connect to make sure they agree on
+// the `.dc` file without exchanging the file itself.
+//
+// Rather than re-deriving the hash by hand for every AST node kind, this
+// pass implements `DCVisitor` and feeds each node's identifiers and
+// numeric properties into the generator as it walks, in the same order
+// Panda3D's parser would have visited them.
+use crate::ast::*;
+use crate::visit::DCVisitor;
+
+/// A 32-bit DC file hash, truncated the same way Panda3D's is.
+pub type DCFileHash = u32;
+
+/// Matches Panda3D's `HashGenerator`: the prime number table is recycled
+/// once it grows this large, to keep it from growing unbounded.
+const MAX_PRIME_NUMBERS: u16 = 10_000;
+
+/// Generates successive prime numbers, caching them as it goes.
+///
+/// Backed by a sieve of Eratosthenes instead of per-candidate trial
+/// division: primes are cached in a `Vec<u32>` (trial division against a
+/// `Vec<u16>` overflows once a cached prime exceeds 255, since 256² doesn't
+/// fit in a `u16`), and the sieve bound doubles whenever the cache runs
+/// short of the requested index.
+struct PrimeNumberGenerator {
+    primes: Vec<u32>,
+    /// The inclusive upper bound the sieve was last run against; `primes`
+    /// holds every prime up to and including this bound.
+    sieve_bound: u32,
+}
+
+impl Default for PrimeNumberGenerator {
+    fn default() -> Self {
+        Self { primes: vec![2_u32], sieve_bound: 2 }
+    }
+}
+
+impl PrimeNumberGenerator {
+    /// Starting sieve bound; large enough to cover `MAX_PRIME_NUMBERS`
+    /// primes in a handful of doublings without over-allocating up front.
+    const INITIAL_SIEVE_BOUND: u32 = 4096;
+
+    /// Runs the sieve of Eratosthenes up to (and including) `bound`,
+    /// returning every prime in that range in ascending order.
+    fn sieve(bound: u32) -> Vec<u32> {
+        let mut is_composite = vec![false; bound as usize + 1];
+        let mut primes = Vec::new();
+
+        for candidate in 2..=bound {
+            if is_composite[candidate as usize] {
+                continue;
+            }
+            primes.push(candidate);
+
+            // Mark composites of `candidate`, starting at its square (in
+            // u64 so the multiply can't overflow a u32 for large primes).
+            let mut multiple = u64::from(candidate) * u64::from(candidate);
+            while multiple <= u64::from(bound) {
+                is_composite[multiple as usize] = true;
+                multiple += u64::from(candidate);
+            }
+        }
+        primes
+    }
+
+    /// Grows the sieve bound (doubling it) until the cache holds at least
+    /// `target_len` primes, then replaces `self.primes` with the result.
+    fn ensure(&mut self, target_len: usize) {
+        let mut bound = self.sieve_bound;
+
+        while self.primes.len() < target_len {
+            bound = if bound < Self::INITIAL_SIEVE_BOUND {
+                Self::INITIAL_SIEVE_BOUND
+            } else {
+                bound.saturating_mul(2)
+            };
+            self.primes = Self::sieve(bound);
+            self.sieve_bound = bound;
+        }
+    }
+
+    /// Returns the nth prime number, as a `u32`. this\[0\] returns 2, this\[1\]
+    /// returns 3; successively larger values of n return larger prime
+    /// numbers, for any n up to `u16::MAX`.
+    fn get_prime(&mut self, n: u16) -> u32 {
+        self.ensure(usize::from(n) + 1);
+        self.primes[usize::from(n)]
+    }
+}
+
+/// Accumulates integers and strings into a running 32-bit hash.
+#[derive(Default)]
+struct DCHashGenerator {
+    hash: i32,
+    index: u16,
+    primes: PrimeNumberGenerator,
+}
+
+impl DCHashGenerator {
+    fn add_int(&mut self, number: i32) {
+        assert!(self.index < MAX_PRIME_NUMBERS);
+
+        // Multiply in i64 so a large prime times a large `number` can't
+        // overflow before it's folded (and truncated to 32 bits) back
+        // into the hash.
+        let prime: i64 = i64::from(self.primes.get_prime(self.index));
+        self.hash = self.hash.wrapping_add((prime * i64::from(number)) as i32);
+        self.index = (self.index + 1) % MAX_PRIME_NUMBERS;
+    }
+
+    fn add_string(&mut self, string: &str) {
+        self.add_int(string.len().try_into().unwrap());
+
+        for byte in string.bytes() {
+            self.add_int(i32::from(byte));
+        }
+    }
+
+    const fn get_hash(&self) -> DCFileHash {
+        self.hash as u32
+    }
+}
+
+/// Walks a parsed [`DCFile`] and folds it into a [`DCHashGenerator`],
+/// producing a hash compatible with legacy Panda3D DC clients.
+#[derive(Default)]
+struct HashVisitor {
+    hashgen: DCHashGenerator,
+}
+
+impl DCVisitor for HashVisitor {
+    fn visit_struct(&mut self, struct_type: &StructType) {
+        self.hashgen.add_string(struct_type.identifier.as_str());
+        self.hashgen.add_int(struct_type.parameters.len().try_into().unwrap());
+
+        crate::visit::walk_struct(self, struct_type);
+    }
+
+    fn visit_distributed_class(&mut self, dclass: &DistributedClassType) {
+        self.hashgen.add_string(dclass.identifier.as_str());
+        self.hashgen.add_int(dclass.field_declarations.len().try_into().unwrap());
+
+        crate::visit::walk_distributed_class(self, dclass);
+    }
+
+    fn visit_molecular_field(&mut self, molecular_field: &MolecularField) {
+        self.hashgen.add_string(molecular_field.identifier.as_str());
+
+        crate::visit::walk_molecular_field(self, molecular_field);
+    }
+
+    fn visit_atomic_field(&mut self, atomic_field: &AtomicField) {
+        self.hashgen.add_string(atomic_field.identifier.as_str());
+        self.hashgen.add_int(atomic_field.parameters.len().try_into().unwrap());
+
+        if let Some(keywords) = &atomic_field.keyword_list {
+            self.visit_keyword_type(keywords);
+        }
+        crate::visit::walk_atomic_field(self, atomic_field);
+    }
+
+    fn visit_parameter_field(&mut self, parameter_field: &ParameterField) {
+        if let Some(keywords) = &parameter_field.keyword_list {
+            self.visit_keyword_type(keywords);
+        }
+        crate::visit::walk_parameter_field(self, parameter_field);
+    }
+
+    fn visit_keyword_type(&mut self, keyword_type: &KeywordType) {
+        match &keyword_type.node {
+            KeywordType_::KeywordType(k) => self.hashgen.add_string(k.as_str()),
+            KeywordType_::KeywordList(ks) => {
+                for k in ks {
+                    self.hashgen.add_string(k.as_str());
+                }
+            }
+        }
+    }
+
+    fn visit_parameter(&mut self, parameter: &Parameter) {
+        match parameter {
+            Parameter::Char(p) => {
+                if let Some(t) = &p.char_type {
+                    self.hashgen.add_string(t.as_str());
+                }
+            }
+            Parameter::Int(p) => {
+                if let Some(t) = &p.int_type {
+                    self.hashgen.add_string(t.as_str());
+                }
+                if let Some(range) = &p.int_range {
+                    self.hashgen.add_int(range.start.try_into().unwrap_or(0));
+                    self.hashgen.add_int(range.end.try_into().unwrap_or(0));
+                }
+                if let Some(constant) = p.int_constant {
+                    self.hashgen.add_int(constant.try_into().unwrap_or(0));
+                }
+            }
+            Parameter::Float(p) => {
+                if let Some(t) = &p.float_type {
+                    self.hashgen.add_string(t.as_str());
+                }
+            }
+            Parameter::Sized(p) => {
+                if let Some(t) = &p.sized_type {
+                    self.hashgen.add_string(t.as_str());
+                }
+                if let Some(size) = p.size_constraint {
+                    self.hashgen.add_int(size.try_into().unwrap_or(0));
+                }
+            }
+            Parameter::Struct(p) => {
+                self.hashgen.add_string(p.identifier1.as_str());
+            }
+            Parameter::Array(p) => {
+                self.visit_data_type(&p.data_type);
+                self.hashgen.add_int(p.array_range.start.try_into().unwrap_or(0));
+                self.hashgen.add_int(p.array_range.end.try_into().unwrap_or(0));
+            }
+        }
+    }
+
+    fn visit_data_type(&mut self, data_type: &DataType) {
+        if let Some(identifier) = &data_type.identifier {
+            self.hashgen.add_string(identifier);
+        }
+    }
+}
+
+/// Computes the legacy 32-bit hash of `file`, as Panda3D's `hashPrimaryKey`
+/// would have for the same parsed `.dc` file.
+pub fn legacy_hash(file: &DCFile) -> DCFileHash {
+    let mut visitor = HashVisitor::default();
+    visitor.visit_file(file);
+    visitor.hashgen.get_hash()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prime_number_generator_exceeds_u16_range_without_overflow() {
+        let mut generator = PrimeNumberGenerator::default();
+        assert_eq!(generator.get_prime(0), 2);
+        // The prime at index 6542 (the 6543rd prime) is 65537, which
+        // doesn't fit in a u16 — trial
+        // division against a `Vec<u16>` would have overflowed computing
+        // its square well before reaching it.
+        assert_eq!(generator.get_prime(6542), 65537);
+    }
+
+    #[test]
+    fn hash_generator_add_int_reaches_max_prime_numbers_without_panicking() {
+        let mut generator = DCHashGenerator::default();
+        for i in 0..MAX_PRIME_NUMBERS {
+            generator.add_int(i32::from(i));
+        }
+    }
+}
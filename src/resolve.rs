@@ -0,0 +1,108 @@
+// DONET SOFTWARE
+// Copyright (c) 2023, Donet Authors.
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3.
+// You should have received a copy of this license along
+// with this source code in a file named "LICENSE."
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program; if not, write to the Free Software Foundation,
+// Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+
+// Cross-references a parsed `DCFile`'s `DCImport`s and struct-typed fields
+// against the struct/dclass declarations in the same file, so a later pass
+// (or an IDE) can jump straight from "import DistributedDonut/AI" to the
+// `DistributedClassType` it names, using `NodeId`s rather than comparing
+// identifiers over and over. Built in two passes over the visitor
+// framework: `DeclCollector` indexes every declaration by name, then
+// `Resolver` walks again, resolving each reference it finds against that
+// index.
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::symbol::Symbol;
+use crate::visit::{self, DCVisitor};
+
+/// Maps every name declared at the top level of a `.dc` file (dclasses and
+/// structs) to the `NodeId` of its declaration.
+#[derive(Default)]
+pub struct DeclTable {
+    dclasses: HashMap<Symbol, NodeId>,
+    structs: HashMap<Symbol, NodeId>,
+}
+
+impl DeclTable {
+    /// Indexes every top-level declaration in `file`.
+    pub fn build(file: &DCFile) -> Self {
+        let mut collector = DeclCollector::default();
+        collector.visit_file(file);
+        collector.decls
+    }
+}
+
+#[derive(Default)]
+struct DeclCollector {
+    decls: DeclTable,
+}
+
+impl DCVisitor for DeclCollector {
+    fn visit_struct(&mut self, struct_type: &StructType) {
+        self.decls.structs.insert(struct_type.identifier, struct_type.id);
+        visit::walk_struct(self, struct_type);
+    }
+
+    fn visit_distributed_class(&mut self, dclass: &DistributedClassType) {
+        self.decls.dclasses.insert(dclass.identifier, dclass.id);
+        visit::walk_distributed_class(self, dclass);
+    }
+}
+
+/// Maps a referencing node's `NodeId` (a `DCImport` or a struct-typed
+/// parameter) to the `NodeId` of the declaration it names.
+pub type Resolutions = HashMap<NodeId, NodeId>;
+
+/// Resolves every `DCImport` and struct-typed parameter in `file` against
+/// `decls`. References that don't resolve (a typo, or a class declared in
+/// another `.dc` file) are simply omitted from the result.
+pub fn resolve(file: &DCFile, decls: &DeclTable) -> Resolutions {
+    let mut resolver = Resolver { decls, resolutions: Resolutions::new() };
+    resolver.visit_file(file);
+    resolver.resolutions
+}
+
+struct Resolver<'a> {
+    decls: &'a DeclTable,
+    resolutions: Resolutions,
+}
+
+impl DCVisitor for Resolver<'_> {
+    fn visit_dc_import(&mut self, dc_import: &DCImport) {
+        if let Some(&target) = self.decls.dclasses.get(&dc_import.class) {
+            self.resolutions.insert(dc_import.id, target);
+        }
+    }
+
+    fn visit_parameter(&mut self, parameter: &Parameter) {
+        match parameter {
+            Parameter::Struct(p) => {
+                if let Some(&target) = self.decls.structs.get(&p.identifier1) {
+                    self.resolutions.insert(p.id, target);
+                }
+            }
+            Parameter::Array(p) if p.data_type.base_type == BaseType::StructType => {
+                if let Some(name) = &p.data_type.identifier {
+                    if let Some(&target) = self.decls.structs.get(&Symbol::from(name.as_str())) {
+                        self.resolutions.insert(p.id, target);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// DONET SOFTWARE
+// Copyright (c) 2023, Donet Authors.
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3.
+// You should have received a copy of this license along
+// with this source code in a file named "LICENSE."
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program; if not, write to the Free Software Foundation,
+// Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+
+// Identifiers in a DC file (dclass names, keyword names, view suffixes,
+// type aliases, ...) recur constantly, so instead of heap-allocating a
+// fresh `String` every time the parser sees one, we intern them into a
+// global table and hand out cheap, `Copy`-able handles instead. Equality
+// between two `Symbol`s is then a pointer compare rather than a byte-wise
+// string compare.
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+
+/// A cheap, `Copy` handle to an interned identifier string. Two `Symbol`s
+/// compare equal if and only if they were interned from equal strings.
+#[derive(Clone, Copy)]
+pub struct Symbol(&'static str);
+
+impl Symbol {
+    /// Recovers the original string slice this symbol was interned from.
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+// Every distinct string interned via `intern` leaks exactly one `&'static
+// str`, so two `Symbol`s interned from equal strings always share the same
+// pointer. That lets equality and hashing compare pointer identity instead
+// of the string's bytes, which is the whole point of interning.
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_ptr().hash(state);
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Symbol {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Symbol {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(intern(&s))
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(s: &str) -> Self {
+        intern(s)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(s: String) -> Self {
+        intern(&s)
+    }
+}
+
+fn table() -> &'static Mutex<HashSet<&'static str>> {
+    static TABLE: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    TABLE.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Interns `s`, returning the existing [`Symbol`] if an equal string has
+/// already been interned, or leaking a new `&'static str` and returning a
+/// fresh one otherwise.
+pub fn intern(s: &str) -> Symbol {
+    let mut table = table().lock().unwrap();
+
+    if let Some(existing) = table.get(s) {
+        return Symbol(existing);
+    }
+    let leaked: &'static str = Box::leak(s.to_string().into_boxed_str());
+    table.insert(leaked);
+    Symbol(leaked)
+}
@@ -0,0 +1,232 @@
+// DONET SOFTWARE
+// Copyright (c) 2023, Donet Authors.
+//
+// This program is free software; you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License version 3.
+// You should have received a copy of this license along
+// with this source code in a file named "LICENSE."
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program; if not, write to the Free Software Foundation,
+// Inc., 51 Franklin Street, Fifth Floor, Boston, MA 02110-1301 USA.
+
+// A reusable, read-only traversal of the `ast` tree. Every downstream pass
+// (validation, hashing, codegen) implements `DCVisitor` and overrides only
+// the node kinds it cares about; the default method bodies call the
+// matching `walk_*` function, which recurses into children and dispatches
+// back into the visitor. This double-dispatch setup mirrors rustc's
+// `ast`/`visit` modules.
+use crate::ast::*;
+
+pub trait DCVisitor: Sized {
+    fn visit_file(&mut self, file: &DCFile) {
+        walk_file(self, file);
+    }
+
+    fn visit_type_decl(&mut self, type_decl: &TypeDecl) {
+        walk_type_decl(self, type_decl);
+    }
+
+    fn visit_keyword_type(&mut self, _keyword_type: &KeywordType) {}
+
+    fn visit_struct(&mut self, struct_type: &StructType) {
+        walk_struct(self, struct_type);
+    }
+
+    fn visit_distributed_class(&mut self, dclass: &DistributedClassType) {
+        walk_distributed_class(self, dclass);
+    }
+
+    fn visit_dc_import(&mut self, _dc_import: &DCImport) {}
+
+    fn visit_type_definition(&mut self, type_definition: &TypeDefinition) {
+        walk_type_definition(self, type_definition);
+    }
+
+    fn visit_field(&mut self, field: &FieldDecl) {
+        walk_field(self, field);
+    }
+
+    fn visit_molecular_field(&mut self, molecular_field: &MolecularField) {
+        walk_molecular_field(self, molecular_field);
+    }
+
+    fn visit_atomic_field(&mut self, atomic_field: &AtomicField) {
+        walk_atomic_field(self, atomic_field);
+    }
+
+    fn visit_parameter_field(&mut self, parameter_field: &ParameterField) {
+        walk_parameter_field(self, parameter_field);
+    }
+
+    fn visit_parameter(&mut self, _parameter: &Parameter) {}
+
+    fn visit_data_type(&mut self, _data_type: &DataType) {}
+}
+
+pub fn walk_file<V: DCVisitor>(visitor: &mut V, file: &DCFile) {
+    for type_decl in &file.type_decl {
+        visitor.visit_type_decl(type_decl);
+    }
+}
+
+pub fn walk_type_decl<V: DCVisitor>(visitor: &mut V, type_decl: &TypeDecl) {
+    match &type_decl.node {
+        TypeDecl_::KeywordType(k) => visitor.visit_keyword_type(k),
+        TypeDecl_::StructType(s) => visitor.visit_struct(s),
+        TypeDecl_::DistributedClassType(dc) => visitor.visit_distributed_class(dc),
+        TypeDecl_::DCImport(dci) => visitor.visit_dc_import(dci),
+        TypeDecl_::TypeDefinition(td) => visitor.visit_type_definition(td),
+    }
+}
+
+pub fn walk_struct<V: DCVisitor>(visitor: &mut V, struct_type: &StructType) {
+    for parameter_field in &struct_type.parameters {
+        visitor.visit_parameter_field(parameter_field);
+    }
+}
+
+pub fn walk_distributed_class<V: DCVisitor>(visitor: &mut V, dclass: &DistributedClassType) {
+    for field in &dclass.field_declarations {
+        visitor.visit_field(field);
+    }
+}
+
+pub fn walk_type_definition<V: DCVisitor>(visitor: &mut V, type_definition: &TypeDefinition) {
+    visitor.visit_data_type(&type_definition.dc_type);
+}
+
+pub fn walk_field<V: DCVisitor>(visitor: &mut V, field: &FieldDecl) {
+    match &field.node {
+        FieldDecl_::MolecularField(mf) => visitor.visit_molecular_field(mf),
+        FieldDecl_::AtomicField(af) => visitor.visit_atomic_field(af),
+        FieldDecl_::ParameterField(pf) => visitor.visit_parameter_field(pf),
+    }
+}
+
+pub fn walk_molecular_field<V: DCVisitor>(visitor: &mut V, molecular_field: &MolecularField) {
+    match &molecular_field.field_type {
+        FieldType::Atomic(af) => visitor.visit_atomic_field(af),
+        FieldType::Parameter(pf) => visitor.visit_parameter_field(pf),
+    }
+}
+
+pub fn walk_atomic_field<V: DCVisitor>(visitor: &mut V, atomic_field: &AtomicField) {
+    for parameter in &atomic_field.parameters {
+        visitor.visit_parameter(parameter);
+    }
+}
+
+pub fn walk_parameter_field<V: DCVisitor>(visitor: &mut V, parameter_field: &ParameterField) {
+    visitor.visit_parameter(&parameter_field.parameter);
+}
+
+/// Mutable counterpart to [`DCVisitor`], used by passes that rewrite the
+/// tree in place (e.g. folding an [`IntTransform`] into a constant).
+pub trait DCMutVisitor: Sized {
+    fn visit_file(&mut self, file: &mut DCFile) {
+        walk_file_mut(self, file);
+    }
+
+    fn visit_type_decl(&mut self, type_decl: &mut TypeDecl) {
+        walk_type_decl_mut(self, type_decl);
+    }
+
+    fn visit_keyword_type(&mut self, _keyword_type: &mut KeywordType) {}
+
+    fn visit_struct(&mut self, struct_type: &mut StructType) {
+        walk_struct_mut(self, struct_type);
+    }
+
+    fn visit_distributed_class(&mut self, dclass: &mut DistributedClassType) {
+        walk_distributed_class_mut(self, dclass);
+    }
+
+    fn visit_dc_import(&mut self, _dc_import: &mut DCImport) {}
+
+    fn visit_type_definition(&mut self, type_definition: &mut TypeDefinition) {
+        walk_type_definition_mut(self, type_definition);
+    }
+
+    fn visit_field(&mut self, field: &mut FieldDecl) {
+        walk_field_mut(self, field);
+    }
+
+    fn visit_molecular_field(&mut self, molecular_field: &mut MolecularField) {
+        walk_molecular_field_mut(self, molecular_field);
+    }
+
+    fn visit_atomic_field(&mut self, atomic_field: &mut AtomicField) {
+        walk_atomic_field_mut(self, atomic_field);
+    }
+
+    fn visit_parameter_field(&mut self, parameter_field: &mut ParameterField) {
+        walk_parameter_field_mut(self, parameter_field);
+    }
+
+    fn visit_parameter(&mut self, _parameter: &mut Parameter) {}
+
+    fn visit_data_type(&mut self, _data_type: &mut DataType) {}
+}
+
+pub fn walk_file_mut<V: DCMutVisitor>(visitor: &mut V, file: &mut DCFile) {
+    for type_decl in &mut file.type_decl {
+        visitor.visit_type_decl(type_decl);
+    }
+}
+
+pub fn walk_type_decl_mut<V: DCMutVisitor>(visitor: &mut V, type_decl: &mut TypeDecl) {
+    match &mut type_decl.node {
+        TypeDecl_::KeywordType(k) => visitor.visit_keyword_type(k),
+        TypeDecl_::StructType(s) => visitor.visit_struct(s),
+        TypeDecl_::DistributedClassType(dc) => visitor.visit_distributed_class(dc),
+        TypeDecl_::DCImport(dci) => visitor.visit_dc_import(dci),
+        TypeDecl_::TypeDefinition(td) => visitor.visit_type_definition(td),
+    }
+}
+
+pub fn walk_struct_mut<V: DCMutVisitor>(visitor: &mut V, struct_type: &mut StructType) {
+    for parameter_field in &mut struct_type.parameters {
+        visitor.visit_parameter_field(parameter_field);
+    }
+}
+
+pub fn walk_distributed_class_mut<V: DCMutVisitor>(visitor: &mut V, dclass: &mut DistributedClassType) {
+    for field in &mut dclass.field_declarations {
+        visitor.visit_field(field);
+    }
+}
+
+pub fn walk_type_definition_mut<V: DCMutVisitor>(visitor: &mut V, type_definition: &mut TypeDefinition) {
+    visitor.visit_data_type(&mut type_definition.dc_type);
+}
+
+pub fn walk_field_mut<V: DCMutVisitor>(visitor: &mut V, field: &mut FieldDecl) {
+    match &mut field.node {
+        FieldDecl_::MolecularField(mf) => visitor.visit_molecular_field(mf),
+        FieldDecl_::AtomicField(af) => visitor.visit_atomic_field(af),
+        FieldDecl_::ParameterField(pf) => visitor.visit_parameter_field(pf),
+    }
+}
+
+pub fn walk_molecular_field_mut<V: DCMutVisitor>(visitor: &mut V, molecular_field: &mut MolecularField) {
+    match &mut molecular_field.field_type {
+        FieldType::Atomic(af) => visitor.visit_atomic_field(af),
+        FieldType::Parameter(pf) => visitor.visit_parameter_field(pf),
+    }
+}
+
+pub fn walk_atomic_field_mut<V: DCMutVisitor>(visitor: &mut V, atomic_field: &mut AtomicField) {
+    for parameter in &mut atomic_field.parameters {
+        visitor.visit_parameter(parameter);
+    }
+}
+
+pub fn walk_parameter_field_mut<V: DCMutVisitor>(visitor: &mut V, parameter_field: &mut ParameterField) {
+    visitor.visit_parameter(&mut parameter_field.parameter);
+}